@@ -1,7 +1,12 @@
 use anyhow::Context;
 use clap::Parser;
-use std::{env, fs, path::PathBuf};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
 
+use crate::dataclasses::FieldList;
 use crate::settings::Settings;
 
 /// Generate a config file from text for Python's Hydra applications"
@@ -46,6 +51,154 @@ pub(crate) fn run() {
     if !quiet {
         println!("{}", toml::to_string_pretty(&settings).unwrap());
     }
+
+    match execute(&args, &settings) {
+        Ok(ok) => {
+            if !ok {
+                std::process::exit(1);
+            }
+        }
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs the formatter over the requested input. Returns `false` when
+/// `--check` found files that would be reformatted (without writing them).
+fn execute(args: &Args, settings: &Settings) -> anyhow::Result<bool> {
+    if args.stdin {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .context("could not read stdin")?;
+        print!("{}", generate(&input, settings)?);
+        return Ok(true);
+    }
+
+    let patterns = args.input_patterns.as_deref().unwrap_or_default();
+    let mut up_to_date = true;
+
+    for path in expand_input_patterns(patterns)? {
+        let input = fs::read_to_string(&path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        let output = generate(&input, settings)?;
+        let output_path = path.with_extension("py");
+
+        if args.check {
+            let existing = fs::read_to_string(&output_path).unwrap_or_default();
+            if existing != output {
+                print!("{}", unified_diff(&existing, &output, &output_path.display().to_string()));
+                up_to_date = false;
+            }
+            continue;
+        }
+
+        fs::write(&output_path, &output)
+            .with_context(|| format!("could not write {}", output_path.display()))?;
+    }
+
+    Ok(up_to_date)
+}
+
+/// Expands space/glob/directory input patterns into a deterministic, deduplicated
+/// list of concrete file paths.
+fn expand_input_patterns(patterns: &[String]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for pattern in patterns {
+        let path = Path::new(pattern);
+        if path.is_dir() {
+            for entry in
+                fs::read_dir(path).with_context(|| format!("could not read {}", path.display()))?
+            {
+                let entry = entry?;
+                if entry.path().is_file() {
+                    files.push(entry.path());
+                }
+            }
+        } else if path.is_file() {
+            files.push(path.to_path_buf());
+        } else {
+            for entry in
+                glob::glob(pattern).with_context(|| format!("invalid glob pattern: {pattern}"))?
+            {
+                files.push(entry?);
+            }
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Runs `input` through the field parser and the dataclass emitter, producing
+/// ready-to-write Python source.
+fn generate(input: &str, settings: &Settings) -> anyhow::Result<String> {
+    let field_list = FieldList::new(input, "", &settings.fields)
+        .map_err(|errors| anyhow::anyhow!("{}", errors.render(input)))?;
+    let body = field_list.render(settings.max_width);
+    Ok(format!("from dataclasses import dataclass, field\n\n\n{body}"))
+}
+
+/// Produces a minimal unified diff between `old` and `new`, labelling both
+/// sides with `path`.
+fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let mut out = format!("--- {path}\n+++ {path}\n");
+    for op in diff_lines(&old_lines, &new_lines) {
+        match op {
+            DiffOp::Equal(line) => out.push_str(&format!(" {line}\n")),
+            DiffOp::Remove(line) => out.push_str(&format!("-{line}\n")),
+            DiffOp::Insert(line) => out.push_str(&format!("+{line}\n")),
+        }
+    }
+    out
+}
+
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Remove(&'a str),
+    Insert(&'a str),
+}
+
+/// A textbook longest-common-subsequence line diff; generated files are small
+/// enough that the O(n*m) table is not a concern.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Remove(old[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|&line| DiffOp::Remove(line)));
+    ops.extend(new[j..].iter().map(|&line| DiffOp::Insert(line)));
+    ops
 }
 
 fn find_config_file() -> anyhow::Result<Option<PathBuf>> {
@@ -111,4 +264,45 @@ mod tests {
         let settings = load_config(&temp_file.path().to_path_buf()).unwrap();
         assert_eq!(settings.max_width, 120);
     }
+
+    #[test]
+    fn test_generate() {
+        let settings = Settings::default();
+        let output = generate("{a=1}{b.c=hello}", &settings).unwrap();
+        assert!(output.starts_with("from dataclasses import dataclass, field\n\n\n"));
+        assert!(output.contains("@dataclass\nclass Config:"));
+        assert!(output.contains("a: int = 1"));
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff("a\nb\nc\n", "a\nx\nc\n", "out.py");
+        assert_eq!(diff, "--- out.py\n+++ out.py\n a\n-b\n+x\n c\n");
+    }
+
+    #[test]
+    fn test_expand_input_patterns_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("a.txt");
+        fs::write(&file_path, "{a=1}").unwrap();
+
+        let patterns = vec![file_path.display().to_string()];
+        let files = expand_input_patterns(&patterns).unwrap();
+        assert_eq!(files, vec![file_path]);
+    }
+
+    #[test]
+    fn test_expand_input_patterns_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "{a=1}").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "{b=2}").unwrap();
+
+        let patterns = vec![temp_dir.path().display().to_string()];
+        let mut files = expand_input_patterns(&patterns).unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![temp_dir.path().join("a.txt"), temp_dir.path().join("b.txt")]
+        );
+    }
 }
@@ -1,4 +1,8 @@
-use crate::params::{Parameter, ParameterReplacer};
+use std::collections::HashMap;
+
+use crate::params::{Parameter, ParameterError, ParameterReplacer};
+use crate::path::SplitAccumulateExt;
+use crate::settings::FieldOverride;
 use crate::types::{is_float, is_int};
 use thiserror::Error;
 
@@ -8,25 +12,123 @@ enum Kind {
     Float,
     String,
     Bool,
+    /// A value whose element kinds could not be unified into anything more precise.
+    Object,
     List(Box<Kind>),
+    Tuple(Vec<Kind>),
+    Dict(Box<Kind>, Box<Kind>),
+    Optional(Box<Kind>),
     Class(String),
 }
 
+/// Splits `s` on top-level commas, i.e. commas not nested inside `[] () {}`.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(s[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = s[start..].trim();
+    if !parts.is_empty() || !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// Splits `s` on the first top-level `:`, used to pull a `key: value` pair
+/// out of a dict literal's entry.
+fn split_top_level_colon(s: &str) -> Option<(&str, &str)> {
+    let mut depth = 0i32;
+    for (index, c) in s.char_indices() {
+        match c {
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' => depth -= 1,
+            ':' if depth == 0 => return Some((s[..index].trim(), s[index + 1..].trim())),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Unifies the kinds of a collection's elements: a shared kind wins outright,
+/// `{Int, Float}` widens to `Float`, a bare `None` element wraps the rest in
+/// `Optional`, and anything else falls back to `Object`.
+fn unify(kinds: Vec<Kind>) -> Kind {
+    if kinds.is_empty() {
+        return Kind::Object;
+    }
+
+    let is_none = |k: &Kind| matches!(k, Kind::Optional(inner) if **inner == Kind::Object);
+    let concrete: Vec<&Kind> = kinds.iter().filter(|k| !is_none(k)).collect();
+    let has_none = concrete.len() != kinds.len();
+
+    let base = if concrete.is_empty() {
+        Kind::Object
+    } else if concrete.iter().all(|k| **k == *concrete[0]) {
+        concrete[0].clone()
+    } else if concrete.iter().all(|k| matches!(k, Kind::Int | Kind::Float)) {
+        Kind::Float
+    } else {
+        Kind::Object
+    };
+
+    if has_none {
+        Kind::Optional(Box::new(base))
+    } else {
+        base
+    }
+}
+
 impl From<&str> for Kind {
     fn from(default: &str) -> Self {
-        match default {
-            d if is_int(d) => Kind::Int,
-            d if is_float(d) => Kind::Float,
-            d => {
-                if d == "True" || d == "False" {
-                    Kind::Bool
-                } else if d.starts_with('[') && d.ends_with(']') {
-                    let inner = &d[1..d.len() - 1];
-                    Kind::List(Box::new(Kind::from(inner)))
-                } else {
-                    Kind::String
-                }
+        let d = default.trim();
+
+        if d == "None" {
+            return Kind::Optional(Box::new(Kind::Object));
+        }
+        if d.starts_with('[') && d.ends_with(']') {
+            let elements = split_top_level(&d[1..d.len() - 1])
+                .into_iter()
+                .map(Kind::from)
+                .collect();
+            return Kind::List(Box::new(unify(elements)));
+        }
+        if d.starts_with('(') && d.ends_with(')') {
+            let slots = split_top_level(&d[1..d.len() - 1])
+                .into_iter()
+                .map(Kind::from)
+                .collect();
+            return Kind::Tuple(slots);
+        }
+        if d.starts_with('{') && d.ends_with('}') {
+            let entries = split_top_level(&d[1..d.len() - 1]);
+            if entries.is_empty() {
+                return Kind::Dict(Box::new(Kind::String), Box::new(Kind::Object));
             }
+            let (keys, values) = entries
+                .into_iter()
+                .filter_map(split_top_level_colon)
+                .map(|(k, v)| (Kind::from(k), Kind::from(v)))
+                .unzip();
+            return Kind::Dict(Box::new(unify(keys)), Box::new(unify(values)));
+        }
+
+        match d {
+            dd if is_int(dd) => Kind::Int,
+            dd if is_float(dd) => Kind::Float,
+            "True" | "False" => Kind::Bool,
+            _ => Kind::String,
         }
     }
 }
@@ -38,12 +140,46 @@ impl std::fmt::Display for Kind {
             Kind::Float => write!(f, "float"),
             Kind::String => write!(f, "str"),
             Kind::Bool => write!(f, "bool"),
+            Kind::Object => write!(f, "object"),
             Kind::List(ref k) => write!(f, "list[{}]", k),
+            Kind::Tuple(ref kinds) if kinds.is_empty() => write!(f, "tuple[()]"),
+            Kind::Tuple(ref kinds) => {
+                let kinds: Vec<String> = kinds.iter().map(Kind::to_string).collect();
+                write!(f, "tuple[{}]", kinds.join(", "))
+            }
+            Kind::Dict(ref k, ref v) => write!(f, "dict[{}, {}]", k, v),
+            Kind::Optional(ref k) => write!(f, "Optional[{}]", k),
             Kind::Class(ref name) => write!(f, "{}", name),
         }
     }
 }
 
+impl Kind {
+    /// Parses a Python type annotation, such as `float` or `Optional[list[int]]`,
+    /// as written in a `textconf.toml` field override. Returns `None` for
+    /// anything it doesn't recognize, leaving the inferred `Kind` in place.
+    fn parse(spec: &str) -> Option<Kind> {
+        let spec = spec.trim();
+        if let Some(inner) = spec
+            .strip_prefix("Optional[")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            return Some(Kind::Optional(Box::new(Kind::parse(inner)?)));
+        }
+        if let Some(inner) = spec.strip_prefix("list[").and_then(|s| s.strip_suffix(']')) {
+            return Some(Kind::List(Box::new(Kind::parse(inner)?)));
+        }
+        match spec {
+            "int" => Some(Kind::Int),
+            "float" => Some(Kind::Float),
+            "str" => Some(Kind::String),
+            "bool" => Some(Kind::Bool),
+            "object" => Some(Kind::Object),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Field {
     name: String,
@@ -51,10 +187,48 @@ pub struct Field {
     default: String,
 }
 
-#[derive(Error, Debug)]
+impl Field {
+    /// Returns the name of the field.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the inferred kind of the field.
+    pub(crate) fn kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// Returns the default value of the field.
+    pub(crate) fn default(&self) -> &str {
+        &self.default
+    }
+
+    /// Applies a user-provided override, letting an explicit type annotation
+    /// or a renamed identifier win over the heuristic `Kind::from` result.
+    ///
+    /// A `rename` only replaces the field's leaf segment, keeping any dotted
+    /// prefix intact, so renaming e.g. `user.age` to `years` still groups the
+    /// field under the `User` class as `user.years` rather than relocating it
+    /// to the top-level class.
+    fn apply_override(&mut self, over: &FieldOverride) {
+        if let Some(kind) = over.kind.as_deref().and_then(Kind::parse) {
+            self.kind = kind;
+        }
+        if let Some(name) = &over.rename {
+            self.name = match self.name.rsplit_once('.') {
+                Some((prefix, _)) => format!("{prefix}.{name}"),
+                None => name.clone(),
+            };
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
 pub enum FieldError {
     #[error("Parameter default is empty: found {0}")]
     EmptyDefault(String),
+    #[error(transparent)]
+    Malformed(#[from] ParameterError),
 }
 
 impl<'a> TryFrom<&'a Parameter<'a>> for Field {
@@ -75,20 +249,110 @@ impl<'a> TryFrom<&'a Parameter<'a>> for Field {
     }
 }
 
+/// A single `FieldError` together with the byte span of the offending
+/// parameter within the original input, so it can be reported with source context.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiagnostic {
+    error: FieldError,
+    span: (usize, usize),
+}
+
+impl FieldDiagnostic {
+    /// Returns the underlying error.
+    pub fn error(&self) -> &FieldError {
+        &self.error
+    }
+
+    /// Returns the `(start, end)` byte range of the offending parameter.
+    pub fn span(&self) -> (usize, usize) {
+        self.span
+    }
+
+    /// Renders the error together with the source line it occurred on,
+    /// with a caret pointing at the offending parameter.
+    pub fn render(&self, source: &str) -> String {
+        let (start, end) = self.span;
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        let line = &source[line_start..line_end];
+        let caret = " ".repeat(start - line_start) + &"^".repeat((end - start).max(1));
+        format!("{}\n{}\n{}", self.error, line, caret)
+    }
+}
+
+/// All the `FieldDiagnostic`s accumulated while building a `FieldList`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldErrors(Vec<FieldDiagnostic>);
+
+impl FieldErrors {
+    /// Returns every diagnostic collected in this pass.
+    pub fn diagnostics(&self) -> &[FieldDiagnostic] {
+        &self.0
+    }
+
+    /// Renders every diagnostic with its surrounding source line and a caret.
+    pub fn render(&self, source: &str) -> String {
+        self.0
+            .iter()
+            .map(|d| d.render(source))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl std::fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} field error(s) found:", self.0.len())?;
+        for diagnostic in &self.0 {
+            writeln!(f, "  {}", diagnostic.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
 pub struct FieldList {
     source: String,
     fields: Vec<Field>,
 }
 
 impl FieldList {
-    pub fn new(input: &str, prefix: &str) -> Result<Self, FieldError> {
+    pub fn new(
+        input: &str,
+        prefix: &str,
+        overrides: &HashMap<String, FieldOverride>,
+    ) -> Result<Self, FieldErrors> {
         let replacer = ParameterReplacer::new(input);
         let source = replacer.replace(prefix);
-        let fields = replacer
-            .parameters_with_default()
-            .into_iter()
-            .map(|p| Field::try_from(p))
-            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut fields = Vec::new();
+        let mut diagnostics = Vec::new();
+        for param in replacer.parameters_with_default() {
+            match Field::try_from(param) {
+                Ok(mut field) => {
+                    if let Some(over) = overrides.get(field.name()) {
+                        field.apply_override(over);
+                    }
+                    fields.push(field);
+                }
+                Err(error) => diagnostics.push(FieldDiagnostic {
+                    error,
+                    span: (param.start(), param.end()),
+                }),
+            }
+        }
+        for error in replacer.parameter_errors().iter().chain(replacer.type_errors()) {
+            diagnostics.push(FieldDiagnostic {
+                error: FieldError::Malformed(error.clone()),
+                span: error.span(),
+            });
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(FieldErrors(diagnostics));
+        }
+
         Ok(FieldList { source, fields })
     }
 
@@ -99,148 +363,294 @@ impl FieldList {
     pub fn fields(&self) -> &[Field] {
         &self.fields
     }
+
+    /// Groups the fields by their dotted name prefix and emits a set of
+    /// `@dataclass` Python classes, nesting a class per prefix level.
+    pub fn to_dataclasses(&self) -> Dataclasses {
+        Dataclasses::from(self.fields())
+    }
+
+    /// Emits the `@dataclass` classes as Python source, wrapping any field
+    /// line with a `list`/`tuple`/`dict` default that would otherwise exceed
+    /// `max_width`; other field lines are emitted whole, whatever their width.
+    pub fn render(&self, max_width: usize) -> String {
+        self.to_dataclasses().render(max_width)
+    }
+}
+
+/// A list of fields belonging to a single `@dataclass`.
+#[derive(Debug, PartialEq, Default)]
+struct Fields {
+    vec: Vec<Field>,
+}
+
+impl std::ops::Deref for Fields {
+    type Target = Vec<Field>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            Kind::Class(ref name) => {
+                write!(f, "{}: {} = field(default_factory={})", self.name, name, name)
+            }
+            Kind::String => write!(f, "{}: {} = \"{}\"", self.name, self.kind, self.default),
+            _ => write!(f, "{}: {} = {}", self.name, self.kind, self.default),
+        }
+    }
+}
+
+impl std::fmt::Display for Fields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: String = self.iter().map(|field| format!("    {}\n", field)).collect();
+        write!(f, "{}", fields)
+    }
+}
+
+impl Field {
+    /// Renders the field's declaration line, wrapping a `list`/`tuple`/`dict`
+    /// default over multiple lines if the indented single-line form would
+    /// exceed `max_width`. Non-collection defaults are never split, since there is
+    /// no boundary to break on without producing invalid Python.
+    fn render(&self, max_width: usize) -> String {
+        let line = self.to_string();
+        if line.len() + 4 <= max_width {
+            return format!("    {}\n", line);
+        }
+
+        let default = self.default.trim();
+        let (open, close) = match (default.chars().next(), default.chars().last()) {
+            (Some(open @ ('[' | '(' | '{')), Some(close @ (']' | ')' | '}'))) => (open, close),
+            _ => return format!("    {}\n", line),
+        };
+
+        let elements = split_top_level(&default[1..default.len() - 1]);
+        let mut out = format!("    {}: {} = {}\n", self.name, self.kind, open);
+        for element in elements {
+            out.push_str(&format!("        {},\n", element));
+        }
+        out.push_str(&format!("    {}\n", close));
+        out
+    }
+}
+
+impl Fields {
+    fn render(&self, max_width: usize) -> String {
+        self.iter().map(|field| field.render(max_width)).collect()
+    }
+}
+
+/// A Python `@dataclass` emitted from a group of fields sharing a dotted name prefix.
+#[derive(Debug, PartialEq, Default)]
+pub struct Dataclass {
+    name: String,
+    fields: Fields,
+}
+
+impl Dataclass {
+    /// The name given to the dataclass generated for top-level (unprefixed) fields.
+    const ROOT: &'static str = "Config";
+
+    fn new(name: &str) -> Self {
+        Dataclass {
+            name: name.to_string(),
+            fields: Fields::default(),
+        }
+    }
+}
+
+impl std::fmt::Display for Dataclass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@dataclass\nclass {}:\n{}", self.name, self.fields)
+    }
+}
+
+impl Dataclass {
+    fn render(&self, max_width: usize) -> String {
+        format!(
+            "@dataclass\nclass {}:\n{}",
+            self.name,
+            self.fields.render(max_width)
+        )
+    }
+}
+
+/// A set of `@dataclass` definitions, topologically ordered so that every
+/// nested class is declared before the class that references it.
+#[derive(Debug, PartialEq, Default)]
+pub struct Dataclasses {
+    vec: Vec<Dataclass>,
+}
+
+impl std::ops::Deref for Dataclasses {
+    type Target = Vec<Dataclass>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl std::fmt::Display for Dataclasses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let body: Vec<String> = self.vec.iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", body.join("\n"))
+    }
+}
+
+impl Dataclasses {
+    /// Renders every dataclass, wrapping field lines wider than `max_width`
+    /// whose default is a `list`/`tuple`/`dict` (see `Field::render`).
+    pub fn render(&self, max_width: usize) -> String {
+        self.vec
+            .iter()
+            .map(|d| d.render(max_width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Converts a single path segment into a Python `PascalCase` class name,
+/// PascalCasing every `_`-separated word and concatenating them (e.g.
+/// `profile_settings` -> `ProfileSettings`).
+fn class_name(path: &str) -> String {
+    path.split(['.', '_'])
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Converts a *full* dotted class path into a `PascalCase` class name used
+/// to disambiguate two paths that share a leaf name (e.g. `train.model` and
+/// `eval.model`). Hierarchy is split only on `.`; each `.`-segment is
+/// capitalized as a whole, with any `_` kept as literal text rather than
+/// also treated as a word boundary. Splitting on both characters here would
+/// let unrelated paths collapse onto the same name (`ab.cd` and `ab_cd`
+/// would both PascalCase to `AbCd`), defeating the whole point of this
+/// fallback.
+fn full_path_class_name(path: &str) -> String {
+    path.split('.')
+        .map(|segment| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+impl From<&[Field]> for Dataclasses {
+    fn from(fields: &[Field]) -> Self {
+        // Classes are first built keyed (and named) by their full dotted
+        // path, which is unique by construction; `class_name` is applied to
+        // the short, user-facing name only in the resolution pass below,
+        // once every path is known and any leaf-name collisions can be seen.
+        let mut classes: HashMap<String, Dataclass> = HashMap::new();
+        classes.insert(String::new(), Dataclass::new(Dataclass::ROOT));
+
+        for field in fields {
+            let Some((prefix, leaf)) = field.name().rsplit_once('.') else {
+                classes
+                    .get_mut("")
+                    .unwrap()
+                    .fields
+                    .vec
+                    .push(field.clone());
+                continue;
+            };
+
+            let mut parent_path = String::new();
+            for path in prefix.split_accumulate('.') {
+                let attr = last_segment(&path).to_string();
+
+                classes
+                    .entry(path.clone())
+                    .or_insert_with(|| Dataclass::new(&path));
+
+                let parent = classes.get_mut(&parent_path).unwrap();
+                if !parent.fields.iter().any(|f| f.name() == attr) {
+                    parent.fields.vec.push(Field {
+                        name: attr,
+                        kind: Kind::Class(path.clone()),
+                        default: String::new(),
+                    });
+                }
+                parent_path = path;
+            }
+
+            let leaf_field = Field {
+                name: leaf.to_string(),
+                kind: field.kind().clone(),
+                default: field.default().to_string(),
+            };
+            classes.get_mut(prefix).unwrap().fields.vec.push(leaf_field);
+        }
+
+        // Resolve every path to its final class name: the short, leaf-based
+        // name where it's unique, or the full path PascalCased where two
+        // different prefixes share a leaf (e.g. `train.model` and
+        // `eval.model` would otherwise both become `Model`).
+        let mut leaf_counts: HashMap<String, usize> = HashMap::new();
+        for path in classes.keys().filter(|path| !path.is_empty()) {
+            *leaf_counts.entry(class_name(last_segment(path))).or_default() += 1;
+        }
+        let names: HashMap<String, String> = classes
+            .keys()
+            .filter(|path| !path.is_empty())
+            .map(|path| {
+                let leaf_name = class_name(last_segment(path));
+                let name = if leaf_counts[&leaf_name] > 1 {
+                    full_path_class_name(path)
+                } else {
+                    leaf_name
+                };
+                (path.clone(), name)
+            })
+            .collect();
+
+        for (path, name) in &names {
+            classes.get_mut(path).unwrap().name = name.clone();
+        }
+        for dataclass in classes.values_mut() {
+            for field in dataclass.fields.vec.iter_mut() {
+                if let Kind::Class(path) = &field.kind {
+                    field.kind = Kind::Class(names[path].clone());
+                }
+            }
+        }
+
+        let mut paths: Vec<String> = classes.keys().cloned().collect();
+        paths.sort_by(|a, b| match (a.is_empty(), b.is_empty()) {
+            (true, true) => std::cmp::Ordering::Equal,
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            (false, false) => {
+                let depth = |s: &str| s.matches('.').count();
+                depth(b).cmp(&depth(a)).then_with(|| a.cmp(b))
+            }
+        });
+
+        let vec = paths
+            .into_iter()
+            .map(|path| classes.remove(&path).unwrap())
+            .collect();
+        Dataclasses { vec }
+    }
 }
 
-// impl Field {
-//     pub fn name(&self) -> &str {
-//         &self.name
-//     }
-//     pub fn default(&self) -> &str {
-//         &self.default
-//     }
-
-//     pub fn remove_prefix(&mut self) -> Option<String> {
-//         match self.name.rsplit_once('.') {
-//             Some((prefix, name)) => {
-//                 let prefix = prefix.to_string();
-//                 self.name = name.to_string();
-//                 Some(prefix)
-//             }
-//             None => None,
-//         }
-//     }
-// }
-
-// impl std::fmt::Display for Field {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         match self.kind {
-//             Kind::String => write!(f, "{}: {} = \"{}\"", self.name, self.kind, self.default),
-//             _ => write!(f, "{}: {} = {}", self.name, self.kind, self.default),
-//         }
-//     }
-// }
-
-// #[derive(Debug, PartialEq, Default)]
-// pub struct Fields {
-//     vec: Vec<Field>,
-// }
-
-// impl std::ops::Deref for Fields {
-//     type Target = Vec<Field>;
-
-//     fn deref(&self) -> &Self::Target {
-//         &self.vec
-//     }
-// }
-
-// impl std::ops::DerefMut for Fields {
-//     fn deref_mut(&mut self) -> &mut Self::Target {
-//         &mut self.vec
-//     }
-// }
-
-// impl From<&Parameters> for Fields {
-//     fn from(value: &Parameters) -> Self {
-//         let vec: Vec<_> = value
-//             .filter_map(|param| Field::try_from(param).ok())
-//             .collect();
-//         Fields { vec }
-//     }
-// }
-
-// impl std::fmt::Display for Fields {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         let fields: String = self.iter().map(|f| format!("    {}\n", f)).collect();
-//         write!(f, "{}", fields)
-//     }
-// }
-
-// #[derive(Debug, PartialEq, Default)]
-// pub struct Dataclass {
-//     name: Option<String>,
-//     fields: Fields,
-// }
-
-// impl Dataclass {
-//     pub fn new(name: Option<&str>) -> Self {
-//         Dataclass {
-//             name: name.map(|s| s.to_string()),
-//             fields: Fields { vec: Vec::new() },
-//         }
-//     }
-// }
-
-// #[derive(Debug, PartialEq, Default)]
-// pub struct Dataclasses {
-//     vec: Vec<Dataclass>,
-// }
-
-// impl std::ops::Deref for Dataclasses {
-//     type Target = Vec<Dataclass>;
-
-//     fn deref(&self) -> &Self::Target {
-//         &self.vec
-//     }
-// }
-
-// impl Dataclasses {
-//     pub fn new() -> Self {
-//         Self::default()
-//     }
-
-//     pub fn get(&self, name: Option<&str>) -> Option<&Dataclass> {
-//         self.vec
-//             .iter()
-//             .find(|&dataclass| dataclass.name.as_deref() == name)
-//     }
-//     pub fn get_mut(&mut self, name: Option<&str>) -> Option<&mut Dataclass> {
-//         self.vec
-//             .iter_mut()
-//             .find(|dataclass| dataclass.name.as_deref() == name)
-//     }
-
-//     pub fn push(&mut self, mut field: Field) {
-//         let prefix = field.remove_prefix();
-//         if let Some(dataclass) = self.get_mut(prefix.as_deref()) {
-//             dataclass.fields.push(field);
-//         } else {
-//             let dataclass = Dataclass::new(prefix.as_deref());
-//             self.vec.push(dataclass);
-//         }
-//     }
-// }
-
-// impl<'a> TryFrom<&'a str> for Dataclass {
-//     type Error = anyhow::Error;
-
-//     fn try_from(value: &str) -> Result<Self, Self::Error> {
-//         let fields = Fields::try_from(value)?;
-//         let name = TARGET_RE
-//             .captures(value)
-//             .and_then(|caps| caps.get(1))
-//             .map(|m| m.as_str().to_string())
-//             .ok_or_else(|| anyhow::anyhow!("{} field not found", Dataclass::TARGET))?;
-
-//         Ok(Dataclass { name, fields })
-//     }
-// }
-
-// impl std::fmt::Display for Dataclass {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         let cls = format!("@dataclass\nclass {}:", self.name);
-//         write!(f, "{}\n{}", cls, self.fields)
-//     }
-// }
 #[cfg(test)]
 mod kind_tests {
     use super::*;
@@ -277,6 +687,109 @@ mod kind_tests {
         assert_eq!(Kind::from("hello"), Kind::String);
     }
 
+    #[test]
+    fn test_kind_from_list_multi_element() {
+        assert_eq!(Kind::from("[1, 2]"), Kind::List(Box::new(Kind::Int)));
+    }
+
+    #[test]
+    fn test_kind_from_list_int_float_widens() {
+        assert_eq!(Kind::from("[1, 2.0]"), Kind::List(Box::new(Kind::Float)));
+    }
+
+    #[test]
+    fn test_kind_from_list_mixed_falls_back_to_object() {
+        assert_eq!(
+            Kind::from("[1, hello]"),
+            Kind::List(Box::new(Kind::Object))
+        );
+    }
+
+    #[test]
+    fn test_kind_from_list_nested() {
+        assert_eq!(
+            Kind::from("[[1, 2], [3, 4]]"),
+            Kind::List(Box::new(Kind::List(Box::new(Kind::Int))))
+        );
+    }
+
+    #[test]
+    fn test_kind_from_list_empty() {
+        assert_eq!(Kind::from("[]"), Kind::List(Box::new(Kind::Object)));
+    }
+
+    #[test]
+    fn test_kind_from_tuple() {
+        assert_eq!(
+            Kind::from("(1, hello)"),
+            Kind::Tuple(vec![Kind::Int, Kind::String])
+        );
+    }
+
+    #[test]
+    fn test_kind_from_tuple_empty() {
+        assert_eq!(Kind::from("()"), Kind::Tuple(vec![]));
+    }
+
+    #[test]
+    fn test_kind_from_dict() {
+        assert_eq!(
+            Kind::from("{1: hello, 2: world}"),
+            Kind::Dict(Box::new(Kind::Int), Box::new(Kind::String))
+        );
+    }
+
+    #[test]
+    fn test_kind_from_dict_empty() {
+        assert_eq!(
+            Kind::from("{}"),
+            Kind::Dict(Box::new(Kind::String), Box::new(Kind::Object))
+        );
+    }
+
+    #[test]
+    fn test_kind_from_none() {
+        assert_eq!(Kind::from("None"), Kind::Optional(Box::new(Kind::Object)));
+    }
+
+    #[test]
+    fn test_kind_from_list_with_none() {
+        assert_eq!(
+            Kind::from("[1, None]"),
+            Kind::List(Box::new(Kind::Optional(Box::new(Kind::Int))))
+        );
+    }
+
+    #[test]
+    fn test_kind_display_tuple() {
+        assert_eq!(
+            Kind::Tuple(vec![Kind::Int, Kind::String]).to_string(),
+            "tuple[int, str]"
+        );
+        assert_eq!(Kind::Tuple(vec![]).to_string(), "tuple[()]");
+    }
+
+    #[test]
+    fn test_kind_display_dict() {
+        assert_eq!(
+            Kind::Dict(Box::new(Kind::Int), Box::new(Kind::String)).to_string(),
+            "dict[int, str]"
+        );
+    }
+
+    #[test]
+    fn test_kind_display_optional() {
+        assert_eq!(
+            Kind::Optional(Box::new(Kind::Int)).to_string(),
+            "Optional[int]"
+        );
+    }
+
+    #[test]
+    fn test_kind_display_object() {
+        assert_eq!(Kind::Object.to_string(), "object");
+    }
+
     #[test]
     fn test_kind_display_int() {
         assert_eq!(Kind::Int.to_string(), "int");
@@ -307,6 +820,32 @@ mod kind_tests {
         assert_eq!(Kind::Class("MyClass".to_string()).to_string(), "MyClass");
     }
 
+    #[test]
+    fn test_kind_parse_scalar() {
+        assert_eq!(Kind::parse("float"), Some(Kind::Float));
+    }
+
+    #[test]
+    fn test_kind_parse_list() {
+        assert_eq!(
+            Kind::parse("list[int]"),
+            Some(Kind::List(Box::new(Kind::Int)))
+        );
+    }
+
+    #[test]
+    fn test_kind_parse_optional() {
+        assert_eq!(
+            Kind::parse("Optional[str]"),
+            Some(Kind::Optional(Box::new(Kind::String)))
+        );
+    }
+
+    #[test]
+    fn test_kind_parse_unrecognized() {
+        assert_eq!(Kind::parse("not_a_type"), None);
+    }
+
     #[test]
     fn test_field_try_from_parameter() {
         let input = "{a}{b=2}{c:.2f=3.0}";
@@ -326,127 +865,300 @@ mod kind_tests {
     fn test_field_list_new() {
         let input = "{a}{b=2}{c.d:.2f=3.0}";
         let source = "{a}{p.b}{p.c.d:.2f}";
-        let field_list = FieldList::new(input, "p.").unwrap();
+        let field_list = FieldList::new(input, "p.", &HashMap::new()).unwrap();
         assert_eq!(field_list.source(), source);
         assert_eq!(field_list.fields().len(), 2);
     }
+
+    #[test]
+    fn test_field_list_new_with_kind_override() {
+        let input = "{a=1}";
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "a".to_string(),
+            FieldOverride {
+                kind: Some("float".to_string()),
+                rename: None,
+            },
+        );
+        let field_list = FieldList::new(input, "", &overrides).unwrap();
+        assert_eq!(field_list.fields()[0].kind, Kind::Float);
+    }
+
+    #[test]
+    fn test_field_list_new_with_rename_override() {
+        let input = "{a=1}";
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "a".to_string(),
+            FieldOverride {
+                kind: None,
+                rename: Some("renamed".to_string()),
+            },
+        );
+        let field_list = FieldList::new(input, "", &overrides).unwrap();
+        assert_eq!(field_list.fields()[0].name, "renamed");
+    }
+
+    #[test]
+    fn test_field_list_new_reports_empty_name() {
+        let input = "{a=1}{=2}";
+        let errors = FieldList::new(input, "", &HashMap::new()).unwrap_err();
+        assert_eq!(
+            errors.diagnostics(),
+            &[FieldDiagnostic {
+                error: FieldError::Malformed(ParameterError::EmptyName {
+                    content: "=2".to_string(),
+                    span: (5, 9),
+                }),
+                span: (5, 9),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_field_list_new_reports_type_mismatch() {
+        let input = "{a=1}{b:d=1.5}";
+        let errors = FieldList::new(input, "", &HashMap::new()).unwrap_err();
+        assert_eq!(
+            errors.diagnostics(),
+            &[FieldDiagnostic {
+                error: FieldError::Malformed(ParameterError::TypeMismatch {
+                    name: "b".to_string(),
+                    default: "1.5".to_string(),
+                    type_char: 'd',
+                    span: (5, 14),
+                }),
+                span: (5, 14),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_field_diagnostic_render() {
+        let input = "x = {a}\n";
+        let replacer = ParameterReplacer::new(input);
+        let param = &replacer.parameters()[0];
+        let error = Field::try_from(param).unwrap_err();
+        assert_eq!(error, FieldError::EmptyDefault("a".to_string()));
+
+        let diagnostic = FieldDiagnostic {
+            error,
+            span: (param.start(), param.end()),
+        };
+        let rendered = diagnostic.render(input);
+        assert_eq!(
+            rendered,
+            "Parameter default is empty: found a\nx = {a}\n    ^^^"
+        );
+    }
+
+    #[test]
+    fn test_field_errors_display() {
+        let errors = FieldErrors(vec![
+            FieldDiagnostic {
+                error: FieldError::EmptyDefault("a".to_string()),
+                span: (0, 3),
+            },
+            FieldDiagnostic {
+                error: FieldError::EmptyDefault("b".to_string()),
+                span: (4, 7),
+            },
+        ]);
+        assert_eq!(
+            errors.to_string(),
+            "2 field error(s) found:\n  Parameter default is empty: found a\n  Parameter default is empty: found b\n"
+        );
+    }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use pretty_assertions::assert_eq;
-
-//     #[test]
-//     fn test_field_display_int() {
-//         let field = Field {
-//             name: "age".to_string(),
-//             kind: Kind::Int,
-//             default: "30".to_string(),
-//         };
-//         assert_eq!(field.to_string(), "age: int = 30");
-//     }
-
-//     #[test]
-//     fn test_field_display_float() {
-//         let field = Field {
-//             name: "price".to_string(),
-//             kind: Kind::Float,
-//             default: "19.99".to_string(),
-//         };
-//         assert_eq!(field.to_string(), "price: float = 19.99");
-//     }
-
-//     #[test]
-//     fn test_field_display_string() {
-//         let field = Field {
-//             name: "name".to_string(),
-//             kind: Kind::String,
-//             default: "John".to_string(),
-//         };
-//         assert_eq!(field.to_string(), "name: str = \"John\"");
-//     }
-
-//     #[test]
-//     fn test_field_display_bool() {
-//         let field = Field {
-//             name: "is_active".to_string(),
-//             kind: Kind::Bool,
-//             default: "True".to_string(),
-//         };
-//         assert_eq!(field.to_string(), "is_active: bool = True");
-//     }
-
-//     #[test]
-//     fn test_field_remove_prefix() {
-//         let mut field = Field {
-//             name: "user.profile.age".to_string(),
-//             kind: Kind::Int,
-//             default: "25".to_string(),
-//         };
-//         let prefix = field.remove_prefix();
-//         assert_eq!(field.name, "age");
-//         assert_eq!(prefix, Some("user.profile".to_string()));
-//     }
-
-//     #[test]
-//     fn test_field_remove_prefix_no_prefix() {
-//         let mut field = Field {
-//             name: "age".to_string(),
-//             kind: Kind::Int,
-//             default: "25".to_string(),
-//         };
-//         let prefix = field.remove_prefix();
-//         assert_eq!(field.name, "age");
-//         assert_eq!(prefix, None);
-//     }
-
-//     #[test]
-//     fn test_dataclasses_get() {
-//         let field = Field {
-//             name: "age".to_string(),
-//             kind: Kind::Int,
-//             default: "25".to_string(),
-//         };
-//         let dataclasses = Dataclasses {
-//             vec: vec![
-//                 Dataclass {
-//                     name: Some("user".to_string()),
-//                     fields: Fields {
-//                         vec: vec![field.clone()],
-//                     },
-//                 },
-//                 Dataclass {
-//                     name: None,
-//                     fields: Fields { vec: vec![field] },
-//                 },
-//             ],
-//         };
-//         assert_eq!(dataclasses.get(Some("user")), Some(&dataclasses[0]));
-//         assert_eq!(dataclasses.get(None), Some(&dataclasses[1]));
-//     }
-
-//     #[test]
-//     fn test_dataclasses_push() {
-//         let mut dataclasses = Dataclasses::new();
-//         let field = Field {
-//             name: "age".to_string(),
-//             kind: Kind::Int,
-//             default: "25".to_string(),
-//         };
-//         dataclasses.push(field);
-//         assert_eq!(dataclasses.get(None), Some(&dataclasses[0]));
-//     }
-
-//     #[test]
-//     fn test_dataclasses_push_with_prefix() {
-//         let mut dataclasses = Dataclasses::new();
-//         let field = Field {
-//             name: "user.profile.age".to_string(),
-//             kind: Kind::Int,
-//             default: "25".to_string(),
-//         };
-//         dataclasses.push(field);
-//         assert_eq!(dataclasses.get(Some("user.profile")), Some(&dataclasses[0]));
-//     }
-// }
+#[cfg(test)]
+mod dataclass_tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_field_display_int() {
+        let field = Field {
+            name: "age".to_string(),
+            kind: Kind::Int,
+            default: "30".to_string(),
+        };
+        assert_eq!(field.to_string(), "age: int = 30");
+    }
+
+    #[test]
+    fn test_field_display_string() {
+        let field = Field {
+            name: "name".to_string(),
+            kind: Kind::String,
+            default: "John".to_string(),
+        };
+        assert_eq!(field.to_string(), "name: str = \"John\"");
+    }
+
+    #[test]
+    fn test_field_display_class() {
+        let field = Field {
+            name: "profile".to_string(),
+            kind: Kind::Class("Profile".to_string()),
+            default: String::new(),
+        };
+        assert_eq!(
+            field.to_string(),
+            "profile: Profile = field(default_factory=Profile)"
+        );
+    }
+
+    #[test]
+    fn test_field_render_fits_on_one_line() {
+        let field = Field {
+            name: "age".to_string(),
+            kind: Kind::Int,
+            default: "30".to_string(),
+        };
+        assert_eq!(field.render(100), "    age: int = 30\n");
+    }
+
+    #[test]
+    fn test_field_render_wraps_long_list() {
+        let field = Field {
+            name: "values".to_string(),
+            kind: Kind::List(Box::new(Kind::Int)),
+            default: "[1, 2, 3]".to_string(),
+        };
+        assert_eq!(
+            field.render(10),
+            "    values: list[int] = [\n        1,\n        2,\n        3,\n    ]\n"
+        );
+    }
+
+    #[test]
+    fn test_field_render_does_not_wrap_non_collection() {
+        let field = Field {
+            name: "name".to_string(),
+            kind: Kind::String,
+            default: "a_very_long_default_value_that_cannot_be_wrapped".to_string(),
+        };
+        assert_eq!(field.render(10), format!("    {}\n", field));
+    }
+
+    #[test]
+    fn test_class_name() {
+        assert_eq!(class_name("profile"), "Profile");
+        assert_eq!(class_name("profile_settings"), "ProfileSettings");
+    }
+
+    #[test]
+    fn test_dataclasses_flat() {
+        let input = "{a=1}{b=hello}";
+        let field_list = FieldList::new(input, "", &HashMap::new()).unwrap();
+        let dataclasses = field_list.to_dataclasses();
+        assert_eq!(dataclasses.len(), 1);
+        assert_eq!(dataclasses[0].name, "Config");
+        assert_eq!(dataclasses[0].fields.len(), 2);
+    }
+
+    #[test]
+    fn test_dataclasses_nested() {
+        let input = "{user.profile.age=30}{user.name=John}";
+        let field_list = FieldList::new(input, "", &HashMap::new()).unwrap();
+        let dataclasses = field_list.to_dataclasses();
+
+        // Children are declared before the parents that reference them.
+        let names: Vec<&str> = dataclasses.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Profile", "User", "Config"]);
+
+        let profile = &dataclasses[0];
+        assert_eq!(profile.fields.len(), 1);
+        assert_eq!(profile.fields[0].to_string(), "age: int = 30");
+
+        let user = &dataclasses[1];
+        assert_eq!(user.fields.len(), 2);
+        assert_eq!(
+            user.fields[0].to_string(),
+            "profile: Profile = field(default_factory=Profile)"
+        );
+        assert_eq!(user.fields[1].to_string(), "name: str = \"John\"");
+
+        let config = &dataclasses[2];
+        assert_eq!(config.fields.len(), 1);
+        assert_eq!(
+            config.fields[0].to_string(),
+            "user: User = field(default_factory=User)"
+        );
+    }
+
+    #[test]
+    fn test_dataclasses_disambiguates_shared_leaf_names() {
+        // `train.model` and `eval.model` share a leaf segment; naming both
+        // classes "Model" would make Python silently rebind the name, so
+        // each must get a distinct, full-path-derived name instead.
+        let input = "{train.model.lr=0.1}{eval.model.lr=0.01}";
+        let field_list = FieldList::new(input, "", &HashMap::new()).unwrap();
+        let dataclasses = field_list.to_dataclasses();
+
+        let names: Vec<&str> = dataclasses.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["EvalModel", "TrainModel", "Eval", "Train", "Config"]);
+
+        let eval = dataclasses.iter().find(|d| d.name == "Eval").unwrap();
+        assert_eq!(
+            eval.fields[0].to_string(),
+            "model: EvalModel = field(default_factory=EvalModel)"
+        );
+
+        let train = dataclasses.iter().find(|d| d.name == "Train").unwrap();
+        assert_eq!(
+            train.fields[0].to_string(),
+            "model: TrainModel = field(default_factory=TrainModel)"
+        );
+    }
+
+    #[test]
+    fn test_dataclasses_full_path_fallback_does_not_collide_on_underscore() {
+        // `ab.cd` and `ab_cd` each have a leaf-collision partner (`xy.cd`
+        // and `z.ab_cd` respectively) that forces both onto the full-path
+        // fallback name. Splitting that fallback on both `.` and `_` would
+        // PascalCase `ab.cd` and `ab_cd` to the same "AbCd", silently
+        // merging two unrelated classes.
+        let input = "{ab.cd.v=1}{xy.cd.v=2}{ab_cd.v=3}{z.ab_cd.v=4}";
+        let field_list = FieldList::new(input, "", &HashMap::new()).unwrap();
+        let dataclasses = field_list.to_dataclasses();
+
+        let names: Vec<&str> = dataclasses.iter().map(|d| d.name.as_str()).collect();
+        let unique: std::collections::HashSet<&str> = names.iter().copied().collect();
+        assert_eq!(names.len(), unique.len(), "class names must be pairwise distinct: {names:?}");
+
+        assert!(names.contains(&"AbCd"));
+        assert!(names.contains(&"Ab_cd"));
+    }
+
+    #[test]
+    fn test_dataclasses_rename_keeps_nested_field_in_its_class() {
+        // Renaming `user.age` to `years` must only replace the leaf segment,
+        // or the field would lose its dotted prefix and silently relocate
+        // from the `User` class to the top-level `Config` class.
+        let input = "{user.age=30}";
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "user.age".to_string(),
+            FieldOverride {
+                kind: None,
+                rename: Some("years".to_string()),
+            },
+        );
+        let field_list = FieldList::new(input, "", &overrides).unwrap();
+        let dataclasses = field_list.to_dataclasses();
+
+        let user = dataclasses.iter().find(|d| d.name == "User").unwrap();
+        assert_eq!(user.fields.len(), 1);
+        assert_eq!(user.fields[0].to_string(), "years: int = 30");
+
+        let config = dataclasses.iter().find(|d| d.name == "Config").unwrap();
+        assert_eq!(config.fields.len(), 1);
+        assert_eq!(
+            config.fields[0].to_string(),
+            "user: User = field(default_factory=User)"
+        );
+    }
+}
@@ -8,6 +8,15 @@
 //! The `ParameterIterator` struct is used to iterate over the parameters in a string.
 //! It can be created from a string slice and provides an iterator over the parameters.
 //!
+//! Beyond the `textconf` CLI's own use of this module (parsing defaults for
+//! dataclass generation, via `ParameterReplacer::parameters_with_default`),
+//! [`ParameterReplacer::render`] and [`ParameterReplacer::render_wrapped`]
+//! are the crate's general-purpose templating API: substituting concrete
+//! `Value`s (or each parameter's own default) into the source text and
+//! applying its `FormatSpec`. Library consumers who want `{name=World}`-style
+//! templates rendered directly, rather than turned into Python dataclasses,
+//! call these instead of going through `dataclasses::FieldList`.
+//!
 //! # Examples
 //!
 //! ```
@@ -21,95 +30,145 @@
 //! assert_eq!(parameters.len(), 3);
 //! ```
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    combinator::value,
+    error::{Error as NomError, ErrorKind},
+    IResult,
+};
 use thiserror::Error;
 
-/// The `Brace` struct represents a section of a string enclosed in curly braces `{}`.
-#[derive(Debug, Copy, Clone, PartialEq)]
-struct Brace<'a> {
-    /// The original string slice.
-    input: &'a str,
-    /// The starting position of the brace.
-    start: usize,
-    /// The ending position of the brace.
-    end: usize,
+use crate::settings::Settings;
+use crate::types::{is_float, is_int};
+
+/// The opening/closing character pair that marks a parameter, generalizing
+/// the `{`/`}` pair used by [`ParameterReplacer::new`]. Passing a different
+/// pair to [`ParameterReplacer::with_delimiters`] lets a template use e.g.
+/// `<name=World>` instead, for input sources where braces already mean
+/// something else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delimiters {
+    pub open: char,
+    pub close: char,
 }
 
-impl<'a> Brace<'a> {
-    /// Returns the content of the brace.
-    pub fn content(&self) -> &'a str {
-        &self.input[self.start + 1..self.end - 1]
+impl Default for Delimiters {
+    fn default() -> Self {
+        Self {
+            open: '{',
+            close: '}',
+        }
     }
 }
 
-/// An iterator over the braces in a string.
-#[derive(Debug, Clone, PartialEq)]
-struct BraceIterator<'a> {
-    /// The original string slice.
-    input: &'a str,
-    /// The current position in the string.
-    start: usize,
+/// Copies `input` onto `result`, collapsing a doubled `delimiters.open` or
+/// `delimiters.close` escape into a single literal occurrence. `input` is
+/// assumed to contain no unescaped parameter braces, i.e. it is text found
+/// between (or around) the parameters already located by `scan`.
+fn push_literal(result: &mut String, input: &str, delimiters: &Delimiters) {
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == delimiters.open && chars.peek() == Some(&delimiters.open) {
+            chars.next();
+            result.push(delimiters.open);
+        } else if c == delimiters.close && chars.peek() == Some(&delimiters.close) {
+            chars.next();
+            result.push(delimiters.close);
+        } else {
+            result.push(c);
+        }
+    }
 }
 
-impl<'a> BraceIterator<'a> {
-    /// Creates a new BraceIterator instance.
-    fn new(input: &'a str) -> Self {
-        BraceIterator { input, start: 0 }
-    }
+/// One lexical unit produced by [`scan`]: plain text, a doubled brace
+/// recognized as a literal escape, or a balanced `{...}` parameter span
+/// together with its byte offsets into the scanned string.
+#[derive(Debug, PartialEq)]
+enum Span<'a> {
+    Text(&'a str),
+    Escaped(char),
+    Param(&'a str, usize, usize),
 }
 
-impl<'a> Iterator for BraceIterator<'a> {
-    type Item = Brace<'a>;
+/// Recognizes a doubled delimiter (`{{` or `}}` by default) as a single literal character.
+fn parse_escaped<'a>(input: &'a str, delimiters: &Delimiters) -> IResult<&'a str, char> {
+    let open: String = [delimiters.open; 2].iter().collect();
+    let close: String = [delimiters.close; 2].iter().collect();
+    alt((
+        value(delimiters.open, tag(open.as_str())),
+        value(delimiters.close, tag(close.as_str())),
+    ))(input)
+}
 
-    /// Returns the next brace in the string.
-    fn next(&mut self) -> Option<Self::Item> {
-        let input = &self.input[self.start..];
-        let mut start = None;
-        let mut prev = None;
-
-        for (index, c) in input.char_indices() {
-            match c {
-                '{' => {
-                    if let Some(prev_char) = prev {
-                        if prev_char == '{' {
-                            prev = None;
-                            start = None;
-                            continue;
-                        }
-                    }
-                    prev = Some(c);
-                    start = Some(index);
-                }
-                '}' => {
-                    if let Some(start_index) = start {
-                        self.start += index + 1;
-                        return Some(Brace {
-                            input: self.input,
-                            start: self.start - (index + 1 - start_index),
-                            end: self.start,
-                        });
-                    }
-                    prev = None;
-                    start = None;
-                }
-                c => {
-                    if c.is_whitespace() {
-                        prev = None;
-                        start = None;
-                    } else if start.is_some() {
-                        prev = Some(c)
-                    }
-                }
+/// Recognizes a run of plain text containing no delimiter characters.
+fn parse_text<'a>(input: &'a str, delimiters: &Delimiters) -> IResult<&'a str, &'a str> {
+    take_till1(|c| c == delimiters.open || c == delimiters.close)(input)
+}
+
+/// Recognizes one balanced `{...}` parameter span, including the delimiters.
+/// Delimiters may nest to any depth inside it; nom has no built-in combinator
+/// for arbitrary-depth balanced delimiters, so the depth is counted by hand.
+fn parse_param_span<'a>(input: &'a str, delimiters: &Delimiters) -> IResult<&'a str, &'a str> {
+    let mut depth = 0;
+    for (index, c) in input.char_indices() {
+        if c == delimiters.open {
+            depth += 1;
+        } else if c == delimiters.close {
+            depth -= 1;
+            if depth == 0 {
+                let end = index + delimiters.close.len_utf8();
+                return Ok((&input[end..], &input[..end]));
             }
         }
-        None
     }
+    Err(nom::Err::Error(NomError::new(input, ErrorKind::Eof)))
 }
 
-impl<'a> From<&'a str> for BraceIterator<'a> {
-    fn from(input: &'a str) -> Self {
-        BraceIterator::new(input)
+/// Scans `input` into a sequence of `Span`s, tracking the absolute byte
+/// offset of each parameter span as it goes. An opening delimiter with no
+/// matching close before the end of `input` is not reported: it and all the
+/// text after it become plain text, so no parameter after it is recognized.
+/// A parameter such as `{a{x}b}` is recognized as a single span covering the
+/// whole balanced region, rather than only its innermost pair.
+fn scan<'a>(input: &'a str, delimiters: &Delimiters) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut rest = input;
+    let mut offset = 0;
+
+    while !rest.is_empty() {
+        if let Ok((tail, c)) = parse_escaped(rest, delimiters) {
+            spans.push(Span::Escaped(c));
+            offset += rest.len() - tail.len();
+            rest = tail;
+        } else if rest.starts_with(delimiters.open) {
+            match parse_param_span(rest, delimiters) {
+                Ok((tail, span)) => {
+                    let open_len = delimiters.open.len_utf8();
+                    let close_len = delimiters.close.len_utf8();
+                    spans.push(Span::Param(
+                        &span[open_len..span.len() - close_len],
+                        offset,
+                        offset + span.len(),
+                    ));
+                    offset += span.len();
+                    rest = tail;
+                }
+                Err(_) => {
+                    spans.push(Span::Text(rest));
+                    break;
+                }
+            }
+        } else {
+            let (tail, text) = parse_text(rest, delimiters).unwrap_or_else(|_| rest.split_at(1));
+            spans.push(Span::Text(text));
+            offset += text.len();
+            rest = tail;
+        }
     }
+    spans
 }
 
 /// A struct representing a parameter in a string.
@@ -121,6 +180,8 @@ pub struct Parameter<'a> {
     start: usize,
     /// The ending position of the brace.
     end: usize,
+    /// The delimiter pair this parameter was scanned with.
+    delimiters: Delimiters,
     /// The name of the parameter.
     name: &'a str,
     /// The format of the parameter.
@@ -142,7 +203,9 @@ impl<'a> Parameter<'a> {
 
     /// Returns the content of the brace.
     pub fn content(&self) -> &str {
-        &self.input[self.start + 1..self.end - 1]
+        let open = self.start + self.delimiters.open.len_utf8();
+        let close = self.end - self.delimiters.close.len_utf8();
+        &self.input[open..close]
     }
 
     /// Returns the name of the parameter.
@@ -162,23 +225,336 @@ impl<'a> Parameter<'a> {
 
     /// Returns the name of the parameter with the format.
     pub fn name_with_format(&self) -> &str {
+        let open = self.start + self.delimiters.open.len_utf8();
         let end = self.end - self.default.map(|d| d.len() + 1).unwrap_or(0);
-        &self.input[self.start + 1..end - 1]
+        let close = end - self.delimiters.close.len_utf8();
+        &self.input[open..close]
+    }
+
+    /// Parses this parameter's format string, if any, into a `FormatSpec`.
+    pub fn format_spec(&self) -> Option<Result<FormatSpec, FormatSpecError>> {
+        self.format.map(FormatSpec::try_from)
     }
+
+    /// Infers this parameter's value type: from its `default` if present
+    /// (via `is_int`/`is_float`), otherwise from its format spec's
+    /// presentation type character (`d` → `Int`; `e`/`E`/`f`/`F`/`g`/`G`/`%`/`n`
+    /// → `Float`; `s` → `Str`).
+    pub fn inferred_type(&self) -> Option<ValueType> {
+        if let Some(default) = self.default {
+            return Some(if is_int(default) {
+                ValueType::Int
+            } else if is_float(default) {
+                ValueType::Float
+            } else {
+                ValueType::Str
+            });
+        }
+        let type_char = self
+            .format
+            .and_then(|f| FormatSpec::try_from(f).ok())
+            .and_then(|spec| spec.type_char())?;
+        match type_char {
+            'd' => Some(ValueType::Int),
+            'e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%' | 'n' => Some(ValueType::Float),
+            's' => Some(ValueType::Str),
+            _ => None,
+        }
+    }
+
+    /// Returns a `ParameterError::TypeMismatch` if this parameter has both a
+    /// `default` and a format type character, and the two disagree (e.g.
+    /// `{x:d=1.5}`, where the default is a float but `d` demands an integer).
+    fn type_mismatch(&self) -> Option<ParameterError> {
+        let default = self.default?;
+        let type_char = self
+            .format
+            .and_then(|f| FormatSpec::try_from(f).ok())
+            .and_then(|spec| spec.type_char())?;
+
+        let is_default_int = is_int(default);
+        let is_default_float = is_float(default);
+        let incompatible = match type_char {
+            'b' | 'c' | 'd' | 'o' | 'x' | 'X' => !is_default_int,
+            'e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%' | 'n' => !(is_default_int || is_default_float),
+            's' => is_default_int || is_default_float,
+            _ => false,
+        };
+
+        incompatible.then(|| ParameterError::TypeMismatch {
+            name: self.name.to_string(),
+            default: default.to_string(),
+            type_char,
+            span: (self.start, self.end),
+        })
+    }
+}
+
+/// The coarse value type inferred for a parameter, used to validate that a
+/// default's shape agrees with its format spec's presentation type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValueType {
+    Int,
+    Float,
+    Str,
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone, PartialEq)]
 pub enum ParameterError {
-    #[error("Parameter name is empty: found {0}")]
-    EmptyName(String),
+    #[error("parameter name is empty: found {content:?} at bytes {}..{}", span.0, span.1)]
+    EmptyName { content: String, span: (usize, usize) },
+    #[error("parameter {name:?} default {default:?} is incompatible with format type {type_char:?} at bytes {}..{}", span.0, span.1)]
+    TypeMismatch {
+        name: String,
+        default: String,
+        type_char: char,
+        span: (usize, usize),
+    },
+}
+
+impl ParameterError {
+    /// Returns the byte span of the offending parameter brace.
+    pub fn span(&self) -> (usize, usize) {
+        match self {
+            ParameterError::EmptyName { span, .. } => *span,
+            ParameterError::TypeMismatch { span, .. } => *span,
+        }
+    }
+}
+
+/// The alignment requested by a `FormatSpec`, corresponding to the
+/// `<`, `>`, `^` and `=` characters of the Python mini-language.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+    AfterSign,
+}
+
+/// The sign handling requested by a `FormatSpec`, corresponding to the
+/// `+`, `-` and ` ` characters of the Python mini-language.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    Minus,
+    Space,
+}
+
+/// An error produced while parsing a `FormatSpec`, together with the byte
+/// offset into the spec at which the problem was found.
+#[derive(Debug, Error, PartialEq)]
+pub enum FormatSpecError {
+    #[error("unknown format type {0:?} at byte {1}")]
+    UnknownType(char, usize),
+    #[error("expected digits after '.' at byte {0}")]
+    EmptyPrecision(usize),
+    #[error("unexpected character {0:?} at byte {1}")]
+    UnexpectedChar(char, usize),
+}
+
+/// A parsed Python-style format spec, as found after the `:` in a parameter
+/// such as `{value:>8.2f}`: `[[fill]align][sign][#][0][width][grouping][.precision][type]`.
+///
+/// Parsing walks the spec left to right with a small state machine: `fill`
+/// is only recognized when immediately followed by an `align` character, and
+/// a leading `0` implies zero-padding, which in turn implies `=` alignment
+/// unless an alignment was already given explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatSpec {
+    fill: Option<char>,
+    align: Option<Align>,
+    sign: Option<Sign>,
+    alternate: bool,
+    zero: bool,
+    width: Option<usize>,
+    grouping: Option<char>,
+    precision: Option<usize>,
+    type_char: Option<char>,
 }
 
-impl<'a> TryFrom<Brace<'a>> for Parameter<'a> {
-    type Error = ParameterError;
+impl FormatSpec {
+    /// Returns the fill character used for padding, if a fill/align pair was given.
+    pub fn fill(&self) -> Option<char> {
+        self.fill
+    }
+
+    /// Returns the requested alignment.
+    pub fn align(&self) -> Option<Align> {
+        self.align
+    }
+
+    /// Returns the requested sign handling.
+    pub fn sign(&self) -> Option<Sign> {
+        self.sign
+    }
+
+    /// Returns whether the alternate form (`#`) was requested.
+    pub fn alternate(&self) -> bool {
+        self.alternate
+    }
+
+    /// Returns whether a leading `0` requested zero-padding.
+    pub fn zero(&self) -> bool {
+        self.zero
+    }
+
+    /// Returns the minimum field width, if given.
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Returns the grouping character (`,` or `_`), if given.
+    pub fn grouping(&self) -> Option<char> {
+        self.grouping
+    }
+
+    /// Returns the requested precision, if given.
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Returns the presentation type character (e.g. `f`, `%`, `x`), if given.
+    pub fn type_char(&self) -> Option<char> {
+        self.type_char
+    }
+}
+
+impl TryFrom<&str> for FormatSpec {
+    type Error = FormatSpecError;
+
+    fn try_from(spec: &str) -> Result<Self, Self::Error> {
+        let chars: Vec<(usize, char)> = spec.char_indices().collect();
+        let mut pos = 0;
+
+        let is_align = |c: char| matches!(c, '<' | '>' | '^' | '=');
+        let to_align = |c: char| match c {
+            '<' => Align::Left,
+            '>' => Align::Right,
+            '^' => Align::Center,
+            '=' => Align::AfterSign,
+            _ => unreachable!(),
+        };
+
+        let mut fill = None;
+        let mut align = None;
+        if pos + 1 < chars.len() && is_align(chars[pos + 1].1) {
+            fill = Some(chars[pos].1);
+            align = Some(to_align(chars[pos + 1].1));
+            pos += 2;
+        } else if pos < chars.len() && is_align(chars[pos].1) {
+            align = Some(to_align(chars[pos].1));
+            pos += 1;
+        }
+
+        let mut sign = None;
+        if let Some(&(_, c)) = chars.get(pos) {
+            sign = match c {
+                '+' => Some(Sign::Plus),
+                '-' => Some(Sign::Minus),
+                ' ' => Some(Sign::Space),
+                _ => None,
+            };
+            if sign.is_some() {
+                pos += 1;
+            }
+        }
+
+        let mut alternate = false;
+        if chars.get(pos).map(|&(_, c)| c) == Some('#') {
+            alternate = true;
+            pos += 1;
+        }
+
+        let mut zero = false;
+        if chars.get(pos).map(|&(_, c)| c) == Some('0') {
+            zero = true;
+            if align.is_none() {
+                align = Some(Align::AfterSign);
+            }
+            pos += 1;
+        }
+
+        let width_start = pos;
+        while chars.get(pos).map(|&(_, c)| c.is_ascii_digit()) == Some(true) {
+            pos += 1;
+        }
+        let width = if pos > width_start {
+            spec[chars[width_start].0..chars[pos - 1].0 + 1]
+                .parse()
+                .ok()
+        } else {
+            None
+        };
+
+        let mut grouping = None;
+        if let Some(&(_, c)) = chars.get(pos) {
+            if c == ',' || c == '_' {
+                grouping = Some(c);
+                pos += 1;
+            }
+        }
+
+        let mut precision = None;
+        if chars.get(pos).map(|&(_, c)| c) == Some('.') {
+            let dot_index = chars[pos].0;
+            pos += 1;
+            let precision_start = pos;
+            while chars.get(pos).map(|&(_, c)| c.is_ascii_digit()) == Some(true) {
+                pos += 1;
+            }
+            if pos == precision_start {
+                return Err(FormatSpecError::EmptyPrecision(dot_index));
+            }
+            precision = spec[chars[precision_start].0..chars[pos - 1].0 + 1]
+                .parse()
+                .ok();
+        }
+
+        let mut type_char = None;
+        if let Some(&(index, c)) = chars.get(pos) {
+            if matches!(
+                c,
+                'b' | 'c' | 'd' | 'e' | 'E' | 'f' | 'F' | 'g' | 'G' | 'n' | 'o' | 's' | 'x' | 'X'
+                    | '%'
+            ) {
+                type_char = Some(c);
+                pos += 1;
+            } else {
+                return Err(FormatSpecError::UnknownType(c, index));
+            }
+        }
+
+        if let Some(&(index, c)) = chars.get(pos) {
+            return Err(FormatSpecError::UnexpectedChar(c, index));
+        }
 
-    fn try_from(brace: Brace<'a>) -> Result<Self, Self::Error> {
-        let content = brace.content();
+        Ok(FormatSpec {
+            fill,
+            align,
+            sign,
+            alternate,
+            zero,
+            width,
+            grouping,
+            precision,
+            type_char,
+        })
+    }
+}
 
+impl<'a> Parameter<'a> {
+    /// Builds a `Parameter` from one `Span::Param` found by `scan`: `input`
+    /// is the whole scanned string, `content` its braces-stripped text,
+    /// `start`/`end` the byte offsets of the enclosing braces within `input`,
+    /// and `delimiters` the pair `scan` was called with.
+    fn from_span(
+        input: &'a str,
+        content: &'a str,
+        start: usize,
+        end: usize,
+        delimiters: Delimiters,
+    ) -> Result<Self, ParameterError> {
         let (name_default, default) = match content.split_once('=') {
             Some((name, default)) => (name, Some(default)),
             None => (content, None),
@@ -190,13 +566,17 @@ impl<'a> TryFrom<Brace<'a>> for Parameter<'a> {
         };
 
         if name.is_empty() {
-            return Err(ParameterError::EmptyName(content.to_string()));
+            return Err(ParameterError::EmptyName {
+                content: content.to_string(),
+                span: (start, end),
+            });
         }
 
         Ok(Parameter {
-            input: brace.input,
-            start: brace.start,
-            end: brace.end,
+            input,
+            start,
+            end,
+            delimiters,
             name,
             format,
             default,
@@ -204,17 +584,39 @@ impl<'a> TryFrom<Brace<'a>> for Parameter<'a> {
     }
 }
 
-/// An iterator over the parameters in a string.
+/// Scans `input` for parameter spans using `delimiters`, splitting the
+/// result into the successfully parsed parameters and the errors found
+/// along the way (e.g. an empty name), each tagged with the byte span of
+/// the offending brace.
+fn parse_all(input: &str, delimiters: &Delimiters) -> (Vec<Parameter<'_>>, Vec<ParameterError>) {
+    let mut parameters = Vec::new();
+    let mut errors = Vec::new();
+
+    for span in scan(input, delimiters) {
+        if let Span::Param(content, start, end) = span {
+            match Parameter::from_span(input, content, start, end, *delimiters) {
+                Ok(param) => parameters.push(param),
+                Err(error) => errors.push(error),
+            }
+        }
+    }
+
+    (parameters, errors)
+}
+
+/// An iterator over the successfully parsed parameters in a string.
+/// Parameters that fail to parse (e.g. an empty name) are dropped from the
+/// iteration; collect them with [`ParameterReplacer::parameter_errors`].
 pub struct ParameterIterator<'a> {
-    /// The iterator over the braces in the string.
-    brace_iter: BraceIterator<'a>,
+    parameters: std::vec::IntoIter<Parameter<'a>>,
 }
 
 impl<'a> ParameterIterator<'a> {
-    /// Creates a new ParameterIterator instance.
+    /// Creates a new ParameterIterator instance, using the default `{`/`}` delimiters.
     pub fn new(input: &'a str) -> Self {
+        let (parameters, _) = parse_all(input, &Delimiters::default());
         ParameterIterator {
-            brace_iter: BraceIterator::new(input),
+            parameters: parameters.into_iter(),
         }
     }
 }
@@ -224,12 +626,7 @@ impl<'a> Iterator for ParameterIterator<'a> {
 
     /// Returns the next parameter in the string.
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(brace) = self.brace_iter.next() {
-            if let Ok(param) = Parameter::try_from(brace) {
-                return Some(param);
-            }
-        }
-        None
+        self.parameters.next()
     }
 }
 
@@ -239,6 +636,301 @@ impl<'a> From<&'a str> for ParameterIterator<'a> {
     }
 }
 
+/// A concrete value supplied for a parameter when rendering, as opposed to
+/// the raw string defaults stored on a `Parameter`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl Value {
+    /// Parses a default string into the most specific `Value` it fits:
+    /// `Int`, then `Float`, falling back to `Str`.
+    fn from_default(default: &str) -> Value {
+        if let Ok(n) = default.parse::<i64>() {
+            Value::Int(n)
+        } else if let Ok(x) = default.parse::<f64>() {
+            Value::Float(x)
+        } else {
+            Value::Str(default.to_string())
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(n) => Some(*n as f64),
+            Value::Float(x) => Some(*x),
+            Value::Str(_) => None,
+        }
+    }
+}
+
+/// An error produced while rendering a `ParameterReplacer` with concrete values.
+#[derive(Debug, Error, PartialEq)]
+pub enum RenderError {
+    #[error("no value or default provided for parameter {0:?}")]
+    MissingValue(String),
+    #[error("invalid format spec for parameter {0:?}: {1}")]
+    InvalidFormat(String, FormatSpecError),
+    #[error("value for parameter {0:?} is incompatible with format type {1:?}")]
+    TypeMismatch(String, char),
+}
+
+/// Groups the digits of `digits` (a run of ASCII decimal digits) into runs of
+/// three, separated by `sep`, as in `1,000,000`.
+fn group_digits(digits: &str, sep: char) -> String {
+    let bytes = digits.as_bytes();
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, b) in bytes.iter().enumerate() {
+        if i > 0 && (bytes.len() - i) % 3 == 0 {
+            result.push(sep);
+        }
+        result.push(*b as char);
+    }
+    result
+}
+
+/// Pads `body` out to `spec`'s width using its fill character and alignment,
+/// inserting the padding immediately after `sign_len` leading sign bytes when
+/// the alignment is `AfterSign` (Python's `=`).
+fn pad(body: String, spec: &FormatSpec, default_align: Align, sign_len: usize) -> String {
+    let Some(width) = spec.width() else {
+        return body;
+    };
+    let len = body.chars().count();
+    if len >= width {
+        return body;
+    }
+    let fill = spec.fill().unwrap_or(if spec.zero() { '0' } else { ' ' });
+    let missing = width - len;
+    match spec.align().unwrap_or(default_align) {
+        Align::Left => body + &fill.to_string().repeat(missing),
+        Align::Right => fill.to_string().repeat(missing) + &body,
+        Align::Center => {
+            let left = missing / 2;
+            let right = missing - left;
+            fill.to_string().repeat(left) + &body + &fill.to_string().repeat(right)
+        }
+        Align::AfterSign => {
+            let (sign, rest) = body.split_at(sign_len);
+            format!("{sign}{}{rest}", fill.to_string().repeat(missing))
+        }
+    }
+}
+
+/// Renders a single `Value` according to an optional `FormatSpec`, validating
+/// that the value's kind is compatible with the spec's presentation type.
+fn format_value(name: &str, value: &Value, spec: Option<&FormatSpec>) -> Result<String, RenderError> {
+    let Some(spec) = spec else {
+        return Ok(match value {
+            Value::Int(n) => n.to_string(),
+            Value::Float(x) => x.to_string(),
+            Value::Str(s) => s.clone(),
+        });
+    };
+
+    let type_char = spec.type_char();
+    match (type_char, value) {
+        (Some('s'), Value::Int(_) | Value::Float(_)) => {
+            return Err(RenderError::TypeMismatch(name.to_string(), 's'));
+        }
+        (Some('b' | 'c' | 'd' | 'o' | 'x' | 'X'), Value::Float(_) | Value::Str(_)) => {
+            return Err(RenderError::TypeMismatch(name.to_string(), type_char.unwrap()));
+        }
+        (Some('e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%' | 'n'), Value::Str(_)) => {
+            return Err(RenderError::TypeMismatch(name.to_string(), type_char.unwrap()));
+        }
+        _ => {}
+    }
+
+    let precision = spec.precision().unwrap_or(6);
+    let is_negative = value.as_f64().is_some_and(|v| v < 0.0);
+    let sign_prefix = if is_negative {
+        ""
+    } else {
+        match spec.sign() {
+            Some(Sign::Plus) => "+",
+            Some(Sign::Space) => " ",
+            _ => "",
+        }
+    };
+
+    let (body, default_align, is_numeric) = match type_char {
+        Some('s') => {
+            let Value::Str(s) = value else { unreachable!() };
+            let s = match spec.precision() {
+                Some(p) => s.chars().take(p).collect(),
+                None => s.clone(),
+            };
+            (s, Align::Left, false)
+        }
+        Some('b' | 'o' | 'x' | 'X' | 'c') => {
+            let Value::Int(n) = value else { unreachable!() };
+            let digits = match type_char.unwrap() {
+                'b' => format!("{:b}", n.unsigned_abs()),
+                'o' => format!("{:o}", n.unsigned_abs()),
+                'x' => format!("{:x}", n.unsigned_abs()),
+                'X' => format!("{:X}", n.unsigned_abs()),
+                'c' => char::from_u32(*n as u32).map(String::from).unwrap_or_default(),
+                _ => unreachable!(),
+            };
+            let sign = if *n < 0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}"), Align::Right, true)
+        }
+        Some('%') => {
+            let v = value.as_f64().unwrap() * 100.0;
+            let digits = format!("{:.*}", precision, v.abs());
+            let digits = group_if_requested(&digits, spec.grouping());
+            let sign = if v < 0.0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}%"), Align::Right, true)
+        }
+        Some('e' | 'E') => {
+            let v = value.as_f64().unwrap();
+            let (mantissa, exponent) = format_scientific(v.abs(), precision);
+            let mut digits = format!("{mantissa}e{}", format_exponent(exponent));
+            if type_char == Some('E') {
+                digits = digits.to_uppercase();
+            }
+            let sign = if v < 0.0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}"), Align::Right, true)
+        }
+        Some('d') => {
+            let Value::Int(n) = value else { unreachable!() };
+            let digits = group_if_requested(&n.unsigned_abs().to_string(), spec.grouping());
+            let sign = if *n < 0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}"), Align::Right, true)
+        }
+        Some('n') => {
+            let body = match value {
+                Value::Int(n) => {
+                    let digits = group_if_requested(&n.unsigned_abs().to_string(), spec.grouping());
+                    let sign = if *n < 0 { "-" } else { sign_prefix };
+                    format!("{sign}{digits}")
+                }
+                Value::Float(x) => {
+                    let digits = format!("{:.*}", precision, x.abs());
+                    let digits = group_if_requested(&digits, spec.grouping());
+                    let sign = if *x < 0.0 { "-" } else { sign_prefix };
+                    format!("{sign}{digits}")
+                }
+                Value::Str(_) => unreachable!("validated above"),
+            };
+            (body, Align::Right, true)
+        }
+        Some('f' | 'F') => {
+            let v = value.as_f64().unwrap();
+            let digits = format!("{:.*}", precision, v.abs());
+            let digits = group_if_requested(&digits, spec.grouping());
+            let sign = if v < 0.0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}"), Align::Right, true)
+        }
+        Some('g' | 'G') => {
+            // Python's general format: `precision` counts significant
+            // digits (0 behaves as 1), switching between fixed and
+            // scientific notation depending on the resulting exponent, and
+            // stripping trailing fractional zeros unless `#` is given.
+            let v = value.as_f64().unwrap();
+            let significant_digits = precision.max(1);
+            let (mantissa, exponent) = format_scientific(v.abs(), significant_digits - 1);
+            let mut digits = if exponent < -4 || exponent >= significant_digits as i32 {
+                let mantissa = if spec.alternate() {
+                    mantissa
+                } else {
+                    strip_trailing_zeros(&mantissa)
+                };
+                format!("{mantissa}e{}", format_exponent(exponent))
+            } else {
+                let decimal_places = (significant_digits as i32 - 1 - exponent).max(0) as usize;
+                let fixed = format!("{:.*}", decimal_places, v.abs());
+                let fixed = if spec.alternate() {
+                    fixed
+                } else {
+                    strip_trailing_zeros(&fixed)
+                };
+                group_if_requested(&fixed, spec.grouping())
+            };
+            if type_char == Some('G') {
+                digits = digits.to_uppercase();
+            }
+            let sign = if v < 0.0 { "-" } else { sign_prefix };
+            (format!("{sign}{digits}"), Align::Right, true)
+        }
+        None => match value {
+            Value::Str(s) => {
+                let s = match spec.precision() {
+                    Some(p) => s.chars().take(p).collect(),
+                    None => s.clone(),
+                };
+                (s, Align::Left, false)
+            }
+            Value::Int(n) => {
+                let digits = group_if_requested(&n.unsigned_abs().to_string(), spec.grouping());
+                let sign = if *n < 0 { "-" } else { sign_prefix };
+                (format!("{sign}{digits}"), Align::Right, true)
+            }
+            Value::Float(x) => {
+                let digits = match spec.precision() {
+                    Some(p) => format!("{:.*}", p, x.abs()),
+                    None => x.abs().to_string(),
+                };
+                let digits = group_if_requested(&digits, spec.grouping());
+                let sign = if *x < 0.0 { "-" } else { sign_prefix };
+                (format!("{sign}{digits}"), Align::Right, true)
+            }
+        },
+        Some(_) => unreachable!("validated above"),
+    };
+
+    let sign_len = if is_numeric {
+        body.len() - body.trim_start_matches(['+', '-', ' ']).len()
+    } else {
+        0
+    };
+    Ok(pad(body, spec, default_align, sign_len))
+}
+
+/// Formats `v` (non-negative) in scientific notation with `frac_digits`
+/// digits after the mantissa's decimal point, returning the mantissa text
+/// and exponent separately so callers can apply Python's exponent
+/// formatting (signed, zero-padded to at least 2 digits) on top.
+fn format_scientific(v: f64, frac_digits: usize) -> (String, i32) {
+    let formatted = format!("{:.*e}", frac_digits, v);
+    let (mantissa, exponent) = formatted.split_once('e').expect("LowerExp always emits 'e'");
+    (mantissa.to_string(), exponent.parse().expect("LowerExp exponent is an integer"))
+}
+
+/// Renders an exponent the way Python's mini-language does: always signed,
+/// zero-padded to at least 2 digits (`5` -> `+05`, `-3` -> `-03`).
+fn format_exponent(exponent: i32) -> String {
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{sign}{:02}", exponent.abs())
+}
+
+/// Strips trailing fractional zeros (and a now-empty decimal point) from a
+/// plain decimal or scientific mantissa, as Python's `'g'`/`'G'` do unless
+/// the `#` alternate flag is given.
+fn strip_trailing_zeros(digits: &str) -> String {
+    if digits.contains('.') {
+        digits.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        digits.to_string()
+    }
+}
+
+/// Inserts grouping separators into the integer portion of `digits` (everything
+/// before a `.`, if any), leaving any fractional part untouched.
+fn group_if_requested(digits: &str, grouping: Option<char>) -> String {
+    let Some(sep) = grouping else {
+        return digits.to_string();
+    };
+    match digits.split_once('.') {
+        Some((int_part, frac_part)) => format!("{}.{}", group_digits(int_part, sep), frac_part),
+        None => group_digits(digits, sep),
+    }
+}
+
 /// A struct that replaces parameters in a string with a specified prefix.
 ///
 /// The `ParameterReplacer` struct is used to find and replace parameters within a string.
@@ -270,14 +962,57 @@ impl<'a> From<&'a str> for ParameterIterator<'a> {
 /// ```
 pub struct ParameterReplacer<'a> {
     input: &'a str,
+    delimiters: Delimiters,
     parameters: Vec<Parameter<'a>>,
+    parameter_errors: Vec<ParameterError>,
+    type_errors: Vec<ParameterError>,
 }
 
 impl<'a> ParameterReplacer<'a> {
-    /// Creates a new ParameterReplacer instance.
+    /// Creates a new ParameterReplacer instance, using the default `{`/`}` delimiters.
+    ///
+    /// This runs two validation passes: `parse_all` reports every parameter
+    /// brace found by `scan` that fails to parse (e.g. an empty name) as a
+    /// [`ParameterError`] with a byte span, collected in
+    /// [`Self::parameter_errors`]; and a second pass flags any successfully
+    /// parsed parameter whose default value is incompatible with its
+    /// format's presentation type (e.g. `{x:d=1.5}`), collected in
+    /// [`Self::type_errors`].
     pub fn new(input: &'a str) -> Self {
-        let parameters = ParameterIterator::new(input).collect();
-        Self { input, parameters }
+        Self::with_delimiters(input, Delimiters::default())
+    }
+
+    /// Creates a new ParameterReplacer instance scanning `input` with a
+    /// custom `delimiters` pair instead of the default `{`/`}`, for sources
+    /// where braces already mean something else (e.g. `<name=World>`).
+    ///
+    /// Runs the same two validation passes as [`Self::new`].
+    pub fn with_delimiters(input: &'a str, delimiters: Delimiters) -> Self {
+        let (parameters, parameter_errors) = parse_all(input, &delimiters);
+        let type_errors = parameters.iter().filter_map(Parameter::type_mismatch).collect();
+        Self {
+            input,
+            delimiters,
+            parameters,
+            parameter_errors,
+            type_errors,
+        }
+    }
+
+    /// Creates a new ParameterReplacer, rejecting `input` outright if any
+    /// parameter brace fails to parse or any default conflicts with its
+    /// format's presentation type, rather than collecting those problems
+    /// into [`Self::parameter_errors`] / [`Self::type_errors`] for the
+    /// caller to inspect after the fact.
+    pub fn try_parse(input: &'a str) -> Result<Self, Vec<ParameterError>> {
+        let replacer = Self::new(input);
+        if replacer.parameter_errors.is_empty() && replacer.type_errors.is_empty() {
+            Ok(replacer)
+        } else {
+            let mut errors = replacer.parameter_errors;
+            errors.extend(replacer.type_errors);
+            Err(errors)
+        }
     }
 
     /// Returns all parameters.
@@ -285,6 +1020,18 @@ impl<'a> ParameterReplacer<'a> {
         &self.parameters
     }
 
+    /// Returns every parameter brace that failed to parse (e.g. an empty
+    /// name), each tagged with the byte span of the offending brace.
+    pub fn parameter_errors(&self) -> &[ParameterError] {
+        &self.parameter_errors
+    }
+
+    /// Returns every `ParameterError::TypeMismatch` found between a
+    /// parameter's default and its format's presentation type.
+    pub fn type_errors(&self) -> &[ParameterError] {
+        &self.type_errors
+    }
+
     /// Returns all parameters with default values.
     pub fn parameters_with_default(&self) -> Vec<&Parameter<'a>> {
         self.parameters
@@ -302,6 +1049,9 @@ impl<'a> ParameterReplacer<'a> {
     }
 
     /// Replaces parameters with a specified prefix, deleting default values.
+    ///
+    /// Literal `{{` and `}}` outside of a parameter are collapsed to a single
+    /// `{` / `}` in the result, mirroring Rust `format!` / Python `str.format`.
     pub fn replace(&self, prefix: &str) -> String {
         let mut result = String::new();
         let mut last_index = 0;
@@ -310,17 +1060,164 @@ impl<'a> ParameterReplacer<'a> {
 
         for param in self.parameters.iter() {
             if names_with_default.contains(&param.name()) {
-                result.push_str(&self.input[last_index..param.start()]);
-                result.push('{');
+                push_literal(&mut result, &self.input[last_index..param.start()], &self.delimiters);
+                result.push(self.delimiters.open);
                 result.push_str(prefix);
                 result.push_str(param.name_with_format());
-                result.push('}');
+                result.push(self.delimiters.close);
                 last_index = param.end();
             }
         }
-        result.push_str(&self.input[last_index..]);
+        push_literal(&mut result, &self.input[last_index..], &self.delimiters);
         result
     }
+
+    /// Renders this input with concrete `values`, falling back to each
+    /// parameter's own default when a value isn't supplied, and applying the
+    /// parsed `FormatSpec` (precision, padding, sign, grouping, percent
+    /// scaling, ...) to the result.
+    pub fn render(&self, values: &HashMap<&str, Value>) -> Result<String, RenderError> {
+        let mut result = String::new();
+        let mut last_index = 0;
+
+        for param in self.parameters.iter() {
+            push_literal(&mut result, &self.input[last_index..param.start()], &self.delimiters);
+
+            let value = match values.get(param.name()) {
+                Some(value) => value.clone(),
+                None => match param.default() {
+                    Some(default) => Value::from_default(default),
+                    None => return Err(RenderError::MissingValue(param.name().to_string())),
+                },
+            };
+
+            let spec = match param.format_spec() {
+                Some(Ok(spec)) => Some(spec),
+                Some(Err(error)) => {
+                    return Err(RenderError::InvalidFormat(param.name().to_string(), error));
+                }
+                None => None,
+            };
+
+            result.push_str(&format_value(param.name(), &value, spec.as_ref())?);
+            last_index = param.end();
+        }
+        push_literal(&mut result, &self.input[last_index..], &self.delimiters);
+        Ok(result)
+    }
+
+    /// Renders this input like [`Self::render`], then wraps the result to
+    /// `settings.max_width` if `settings.wrap` is set, breaking only on
+    /// whitespace that was already present in the source text. A
+    /// parameter's rendered value is never split across a break, even when
+    /// it contains whitespace of its own, mirroring the way
+    /// [`Self::replace`] treats a `{...}` span as a single unit.
+    pub fn render_wrapped(&self, values: &HashMap<&str, Value>, settings: &Settings) -> Result<String, RenderError> {
+        if !settings.wrap {
+            return self.render(values);
+        }
+
+        let mut wrapper = Wrapper::new(settings.max_width);
+        let mut last_index = 0;
+
+        for param in self.parameters.iter() {
+            let mut literal = String::new();
+            push_literal(&mut literal, &self.input[last_index..param.start()], &self.delimiters);
+            wrapper.push_text(&literal);
+
+            let value = match values.get(param.name()) {
+                Some(value) => value.clone(),
+                None => match param.default() {
+                    Some(default) => Value::from_default(default),
+                    None => return Err(RenderError::MissingValue(param.name().to_string())),
+                },
+            };
+
+            let spec = match param.format_spec() {
+                Some(Ok(spec)) => Some(spec),
+                Some(Err(error)) => {
+                    return Err(RenderError::InvalidFormat(param.name().to_string(), error));
+                }
+                None => None,
+            };
+
+            wrapper.push_atom(&format_value(param.name(), &value, spec.as_ref())?);
+            last_index = param.end();
+        }
+
+        let mut literal = String::new();
+        push_literal(&mut literal, &self.input[last_index..], &self.delimiters);
+        wrapper.push_text(&literal);
+
+        Ok(wrapper.finish())
+    }
+}
+
+/// Accumulates a greedy word-wrap of `max_width`, fed alternately with
+/// breakable literal text ([`Self::push_text`]) and non-breakable rendered
+/// values ([`Self::push_atom`]). A word that alone exceeds `max_width` (or
+/// an atom glued onto one) is still emitted whole, since there is no
+/// boundary to break on without losing text.
+struct Wrapper {
+    max_width: usize,
+    out: String,
+    line_len: usize,
+    word: String,
+}
+
+impl Wrapper {
+    fn new(max_width: usize) -> Self {
+        Self {
+            max_width,
+            out: String::new(),
+            line_len: 0,
+            word: String::new(),
+        }
+    }
+
+    /// Appends a literal chunk of text, splitting it into words at runs of
+    /// whitespace and flushing the in-progress word at each boundary.
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.flush_word();
+            } else {
+                self.word.push(c);
+            }
+        }
+    }
+
+    /// Appends a rendered parameter value onto the in-progress word without
+    /// treating any whitespace it contains as a break point.
+    fn push_atom(&mut self, atom: &str) {
+        self.word.push_str(atom);
+    }
+
+    fn flush_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+
+        let word_len = self.word.chars().count();
+        if self.line_len == 0 {
+            self.out.push_str(&self.word);
+            self.line_len = word_len;
+        } else if self.line_len + 1 + word_len > self.max_width {
+            self.out.push('\n');
+            self.out.push_str(&self.word);
+            self.line_len = word_len;
+        } else {
+            self.out.push(' ');
+            self.out.push_str(&self.word);
+            self.line_len += 1 + word_len;
+        }
+        self.word.clear();
+    }
+
+    fn finish(mut self) -> String {
+        self.flush_word();
+        self.out
+    }
 }
 
 #[cfg(test)]
@@ -330,22 +1227,63 @@ mod tests {
     use rstest::rstest;
 
     #[test]
-    fn brace_iter_simple() {
-        let braces = BraceIterator::new("abc{def}ghi{jkl}mno");
-        let vec: Vec<_> = braces.map(|brace| brace.content()).collect();
-        assert_eq!(vec, vec!["def", "jkl"]);
+    fn scan_simple() {
+        let spans = scan("abc{def}ghi{jkl}mno", &Delimiters::default());
+        assert_eq!(
+            spans,
+            vec![
+                Span::Text("abc"),
+                Span::Param("def", 3, 8),
+                Span::Text("ghi"),
+                Span::Param("jkl", 11, 16),
+                Span::Text("mno"),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_nested_braces_form_one_span() {
+        // A single unescaped brace pair nests arbitrarily deep, forming one
+        // span instead of yielding only its innermost content.
+        let spans = scan("{a{x}b{y}c}", &Delimiters::default());
+        assert_eq!(spans, vec![Span::Param("a{x}b{y}c", 0, 11)]);
     }
 
     #[rstest]
-    #[case("{a{x}b{y}c}")]
-    #[case("{{a{x}b{y}c}}")]
-    #[case("a{{{x}}}b{{{y}}}c{{z}}d{{{{z}}}}")]
-    #[case("{a{{{x}}}b{{{y}}}c{{z}}d}")]
-    fn brace_iter_nested(#[case] input: &str) {
-        let braces = BraceIterator::new(input);
-        let vec: Vec<_> = braces.map(|brace| brace.content()).collect();
-        assert_eq!(vec, vec!["x", "y"]);
+    #[case("{{x}}")]
+    #[case("{{")]
+    #[case("}}")]
+    fn scan_doubled_braces_are_escapes_not_params(#[case] input: &str) {
+        let (parameters, errors) = parse_all(input, &Delimiters::default());
+        assert!(parameters.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn scan_unterminated_brace_is_plain_text() {
+        let spans = scan("abc{x", &Delimiters::default());
+        assert_eq!(spans, vec![Span::Text("abc"), Span::Text("{x")]);
+    }
+
+    #[test]
+    fn parse_all_reports_empty_name_with_span() {
+        let (parameters, errors) = parse_all("a{=1}b", &Delimiters::default());
+        assert!(parameters.is_empty());
+        assert_eq!(
+            errors,
+            vec![ParameterError::EmptyName {
+                content: "=1".to_string(),
+                span: (1, 5),
+            }]
+        );
+    }
+
+    #[test]
+    fn replacer_parameter_errors_is_empty_for_well_formed_input() {
+        let replacer = ParameterReplacer::new("{a}{b=2}");
+        assert!(replacer.parameter_errors().is_empty());
     }
+
     #[test]
     fn test_parameter_creation() {
         let input = "{test}{test_default=default}{test_format:.2f}{test_both:.2f=3.14}";
@@ -439,4 +1377,381 @@ mod tests {
         let result = replacer.replace(prefix);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case("{{{a=1}}}", "p.", "{{p.a}}")]
+    #[case("{{a}}", "p.", "{a}")]
+    #[case("{{", "p.", "{")]
+    #[case("}}", "p.", "}")]
+    #[case("{{{a=1}}}{{{b=2}}}", "p.", "{{p.a}}{{p.b}}")]
+    fn test_replace_escaped_braces(
+        #[case] input: &str,
+        #[case] prefix: &str,
+        #[case] expected: &str,
+    ) {
+        let replacer = ParameterReplacer::new(input);
+        let result = replacer.replace(prefix);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_spec_plain_type() {
+        let spec = FormatSpec::try_from("f").unwrap();
+        assert_eq!(spec.type_char(), Some('f'));
+        assert_eq!(spec.width(), None);
+        assert_eq!(spec.precision(), None);
+    }
+
+    #[test]
+    fn test_format_spec_precision_and_type() {
+        let spec = FormatSpec::try_from(".2f").unwrap();
+        assert_eq!(spec.precision(), Some(2));
+        assert_eq!(spec.type_char(), Some('f'));
+    }
+
+    #[test]
+    fn test_format_spec_percent() {
+        let spec = FormatSpec::try_from(".2%").unwrap();
+        assert_eq!(spec.precision(), Some(2));
+        assert_eq!(spec.type_char(), Some('%'));
+    }
+
+    #[test]
+    fn test_format_spec_fill_align() {
+        let spec = FormatSpec::try_from("*>10").unwrap();
+        assert_eq!(spec.fill(), Some('*'));
+        assert_eq!(spec.align(), Some(Align::Right));
+        assert_eq!(spec.width(), Some(10));
+    }
+
+    #[test]
+    fn test_format_spec_align_without_fill() {
+        let spec = FormatSpec::try_from("^8").unwrap();
+        assert_eq!(spec.fill(), None);
+        assert_eq!(spec.align(), Some(Align::Center));
+        assert_eq!(spec.width(), Some(8));
+    }
+
+    #[test]
+    fn test_format_spec_zero_implies_after_sign_align() {
+        let spec = FormatSpec::try_from("010d").unwrap();
+        assert_eq!(spec.align(), Some(Align::AfterSign));
+        assert_eq!(spec.width(), Some(10));
+        assert_eq!(spec.type_char(), Some('d'));
+    }
+
+    #[test]
+    fn test_format_spec_sign_and_alternate() {
+        let spec = FormatSpec::try_from("+#x").unwrap();
+        assert_eq!(spec.sign(), Some(Sign::Plus));
+        assert!(spec.alternate());
+        assert_eq!(spec.type_char(), Some('x'));
+    }
+
+    #[test]
+    fn test_format_spec_grouping() {
+        let spec = FormatSpec::try_from(",.2f").unwrap();
+        assert_eq!(spec.grouping(), Some(','));
+        assert_eq!(spec.precision(), Some(2));
+    }
+
+    #[test]
+    fn test_format_spec_full() {
+        let spec = FormatSpec::try_from("*>+#010,.2f").unwrap();
+        assert_eq!(spec.fill(), Some('*'));
+        assert_eq!(spec.align(), Some(Align::Right));
+        assert_eq!(spec.sign(), Some(Sign::Plus));
+        assert!(spec.alternate());
+        assert_eq!(spec.width(), Some(10));
+        assert_eq!(spec.grouping(), Some(','));
+        assert_eq!(spec.precision(), Some(2));
+        assert_eq!(spec.type_char(), Some('f'));
+    }
+
+    #[test]
+    fn test_format_spec_empty() {
+        let spec = FormatSpec::try_from("").unwrap();
+        assert_eq!(spec.align(), None);
+        assert_eq!(spec.width(), None);
+        assert_eq!(spec.type_char(), None);
+    }
+
+    #[test]
+    fn test_format_spec_unknown_type() {
+        let error = FormatSpec::try_from(".2q").unwrap_err();
+        assert_eq!(error, FormatSpecError::UnknownType('q', 2));
+    }
+
+    #[test]
+    fn test_format_spec_empty_precision() {
+        let error = FormatSpec::try_from(".").unwrap_err();
+        assert_eq!(error, FormatSpecError::EmptyPrecision(0));
+    }
+
+    #[test]
+    fn test_format_spec_trailing_garbage() {
+        let error = FormatSpec::try_from("fx").unwrap_err();
+        assert_eq!(error, FormatSpecError::UnexpectedChar('x', 1));
+    }
+
+    #[test]
+    fn test_parameter_format_spec() {
+        let input = "{value:.2f=1.0}";
+        let params: Vec<Parameter> = ParameterIterator::from(input).collect();
+        let spec = params[0].format_spec().unwrap().unwrap();
+        assert_eq!(spec.precision(), Some(2));
+        assert_eq!(spec.type_char(), Some('f'));
+    }
+
+    #[test]
+    fn test_parameter_format_spec_none() {
+        let input = "{value=1}";
+        let params: Vec<Parameter> = ParameterIterator::from(input).collect();
+        assert_eq!(params[0].format_spec().is_none(), true);
+    }
+
+    #[test]
+    fn test_render_uses_supplied_value_over_default() {
+        let replacer = ParameterReplacer::new("Hello, {name=World}!");
+        let mut values = HashMap::new();
+        values.insert("name", Value::Str("Rust".to_string()));
+        assert_eq!(replacer.render(&values).unwrap(), "Hello, Rust!");
+    }
+
+    #[test]
+    fn test_render_falls_back_to_default() {
+        let replacer = ParameterReplacer::new("Hello, {name=World}!");
+        assert_eq!(replacer.render(&HashMap::new()).unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn test_render_missing_value_errors() {
+        let replacer = ParameterReplacer::new("{name}");
+        let error = replacer.render(&HashMap::new()).unwrap_err();
+        assert_eq!(error, RenderError::MissingValue("name".to_string()));
+    }
+
+    #[test]
+    fn test_render_wrapped_breaks_at_whitespace() {
+        let replacer = ParameterReplacer::new("one two three four five");
+        let settings = Settings {
+            max_width: 10,
+            ..Settings::default()
+        };
+        let wrapped = replacer.render_wrapped(&HashMap::new(), &settings).unwrap();
+        assert_eq!(wrapped, "one two\nthree four\nfive");
+    }
+
+    #[test]
+    fn test_render_wrapped_keeps_value_intact() {
+        let replacer = ParameterReplacer::new("x = {value}");
+        let settings = Settings {
+            max_width: 10,
+            ..Settings::default()
+        };
+        let mut values = HashMap::new();
+        values.insert("value", Value::Str("a long unbreakable value".to_string()));
+        let wrapped = replacer.render_wrapped(&values, &settings).unwrap();
+        assert_eq!(wrapped, "x =\na long unbreakable value");
+    }
+
+    #[test]
+    fn test_render_wrapped_disabled_matches_render() {
+        let replacer = ParameterReplacer::new("one two three");
+        let settings = Settings {
+            wrap: false,
+            max_width: 5,
+            ..Settings::default()
+        };
+        let wrapped = replacer.render_wrapped(&HashMap::new(), &settings).unwrap();
+        assert_eq!(wrapped, replacer.render(&HashMap::new()).unwrap());
+    }
+
+    #[test]
+    fn test_render_precision_float() {
+        let replacer = ParameterReplacer::new("{x:.2f}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Float(3.14159));
+        assert_eq!(replacer.render(&values).unwrap(), "3.14");
+    }
+
+    #[test]
+    fn test_render_percent() {
+        let replacer = ParameterReplacer::new("{x:.1%}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Float(0.256));
+        assert_eq!(replacer.render(&values).unwrap(), "25.6%");
+    }
+
+    #[test]
+    fn test_render_width_fill_align() {
+        let replacer = ParameterReplacer::new("{x:*>6}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(42));
+        assert_eq!(replacer.render(&values).unwrap(), "****42");
+    }
+
+    #[test]
+    fn test_render_sign_plus() {
+        let replacer = ParameterReplacer::new("{x:+d}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(7));
+        assert_eq!(replacer.render(&values).unwrap(), "+7");
+    }
+
+    #[test]
+    fn test_render_grouping() {
+        let replacer = ParameterReplacer::new("{x:,d}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(1_000_000));
+        assert_eq!(replacer.render(&values).unwrap(), "1,000,000");
+    }
+
+    #[test]
+    fn test_render_zero_pad() {
+        let replacer = ParameterReplacer::new("{x:010d}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(42));
+        assert_eq!(replacer.render(&values).unwrap(), "0000000042");
+    }
+
+    #[test]
+    fn test_render_hex() {
+        let replacer = ParameterReplacer::new("{x:x}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(255));
+        assert_eq!(replacer.render(&values).unwrap(), "ff");
+    }
+
+    #[test]
+    fn test_render_type_mismatch_str_for_float() {
+        let replacer = ParameterReplacer::new("{x:.2f}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Str("abc".to_string()));
+        let error = replacer.render(&values).unwrap_err();
+        assert_eq!(error, RenderError::TypeMismatch("x".to_string(), 'f'));
+    }
+
+    #[test]
+    fn test_render_type_mismatch_number_for_str() {
+        let replacer = ParameterReplacer::new("{x:s}");
+        let mut values = HashMap::new();
+        values.insert("x", Value::Int(1));
+        let error = replacer.render(&values).unwrap_err();
+        assert_eq!(error, RenderError::TypeMismatch("x".to_string(), 's'));
+    }
+
+    #[rstest]
+    #[case("{x=1}", Some(ValueType::Int))]
+    #[case("{x=1.5}", Some(ValueType::Float))]
+    #[case("{x=hello}", Some(ValueType::Str))]
+    #[case("{x:d}", Some(ValueType::Int))]
+    #[case("{x:.2f}", Some(ValueType::Float))]
+    #[case("{x:.1%}", Some(ValueType::Float))]
+    #[case("{x:s}", Some(ValueType::Str))]
+    #[case("{x}", None)]
+    fn test_inferred_type(#[case] input: &str, #[case] expected: Option<ValueType>) {
+        let params: Vec<Parameter> = ParameterIterator::from(input).collect();
+        assert_eq!(params[0].inferred_type(), expected);
+    }
+
+    #[test]
+    fn test_inferred_type_default_wins_over_format() {
+        let params: Vec<Parameter> = ParameterIterator::from("{x:.2f=1}").collect();
+        assert_eq!(params[0].inferred_type(), Some(ValueType::Int));
+    }
+
+    #[test]
+    fn test_replacer_new_flags_type_mismatch() {
+        let replacer = ParameterReplacer::new("{x:d=1.5}");
+        assert_eq!(
+            replacer.type_errors(),
+            &[ParameterError::TypeMismatch {
+                name: "x".to_string(),
+                default: "1.5".to_string(),
+                type_char: 'd',
+                span: (0, 9),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_replacer_new_no_mismatch_for_compatible_default() {
+        let replacer = ParameterReplacer::new("{x:.2f=1}{y:d=2}{z:s=hi}");
+        assert!(replacer.type_errors().is_empty());
+    }
+
+    #[test]
+    fn test_with_delimiters_parses_custom_pair() {
+        let delimiters = Delimiters { open: '<', close: '>' };
+        let replacer = ParameterReplacer::with_delimiters("Hello, <name=World>!", delimiters);
+        assert_eq!(replacer.render(&HashMap::new()).unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn test_with_delimiters_replace_uses_same_pair() {
+        let delimiters = Delimiters { open: '<', close: '>' };
+        let replacer = ParameterReplacer::with_delimiters("<a><b=2>", delimiters);
+        assert_eq!(replacer.replace("p."), "<a><p.b>");
+    }
+
+    #[test]
+    fn test_try_parse_ok_for_well_formed_input() {
+        let replacer = ParameterReplacer::try_parse("{a}{b=2}").unwrap();
+        assert_eq!(replacer.parameters().len(), 2);
+    }
+
+    #[test]
+    fn test_try_parse_collects_all_errors() {
+        let errors = ParameterReplacer::try_parse("{=1}{x:d=1.5}").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ParameterError::EmptyName {
+                    content: "=1".to_string(),
+                    span: (0, 4),
+                },
+                ParameterError::TypeMismatch {
+                    name: "x".to_string(),
+                    default: "1.5".to_string(),
+                    type_char: 'd',
+                    span: (4, 13),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(314159.0, ".2e", "3.14e+05")]
+    #[case(0.0001234, ".2e", "1.23e-04")]
+    #[case(1.0, ".0e", "1e+00")]
+    fn test_render_exponential_matches_python(
+        #[case] input: f64,
+        #[case] format: &str,
+        #[case] expected: &str,
+    ) {
+        let source = format!("{{x:{format}}}");
+        let replacer = ParameterReplacer::new(&source);
+        let mut values = HashMap::new();
+        values.insert("x", Value::Float(input));
+        assert_eq!(replacer.render(&values).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(1234.5678, ".3g", "1.23e+03")]
+    #[case(3.14, ".3g", "3.14")]
+    #[case(0.0001234, ".3g", "0.000123")]
+    #[case(0.000001234, ".3g", "1.23e-06")]
+    #[case(0.0, ".6g", "0")]
+    fn test_render_general_matches_python(
+        #[case] input: f64,
+        #[case] format: &str,
+        #[case] expected: &str,
+    ) {
+        let source = format!("{{x:{format}}}");
+        let replacer = ParameterReplacer::new(&source);
+        let mut values = HashMap::new();
+        values.insert("x", Value::Float(input));
+        assert_eq!(replacer.render(&values).unwrap(), expected);
+    }
 }
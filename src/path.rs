@@ -24,7 +24,7 @@
 //! ```
 
 /// A struct that accumulates parts of a string split by a delimiter.
-struct SplitAccumulate<'a> {
+pub(crate) struct SplitAccumulate<'a> {
     /// The character used to split the string.
     delimiter: char,
     /// An iterator over the parts of the string split by the delimiter.
@@ -62,7 +62,7 @@ impl<'a> Iterator for SplitAccumulate<'a> {
 }
 
 /// SplitAccumulateExt trait adds the split_accumulate method to the str type.
-trait SplitAccumulateExt {
+pub(crate) trait SplitAccumulateExt {
     fn split_accumulate(&self, delimiter: char) -> SplitAccumulate;
 }
 
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::cli::Args;
@@ -7,11 +9,19 @@ use crate::cli::Args;
 pub(crate) struct Settings {
     /// Maximum width of each line
     pub max_width: usize,
+    /// Whether `ParameterReplacer::render_wrapped` should wrap lines to `max_width`.
+    pub wrap: bool,
+    /// Per-field type and naming overrides, keyed by dotted field path.
+    pub fields: HashMap<String, FieldOverride>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
-        Self { max_width: 100 }
+        Self {
+            max_width: 100,
+            wrap: true,
+            fields: HashMap::new(),
+        }
     }
 }
 
@@ -20,3 +30,17 @@ impl Settings {
         Settings::default()
     }
 }
+
+/// A user-provided override for a single field, read from the `[fields.*]`
+/// tables in `textconf.toml`. Explicit overrides win over the inferred `Kind`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct FieldOverride {
+    /// A Python type annotation (e.g. `float`, `Optional[str]`) that replaces
+    /// the inferred `Kind` for this field.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// The Python identifier to emit for this field. It replaces only the last
+    /// segment of the dotted source name, so the field stays in its class.
+    pub rename: Option<String>,
+}